@@ -0,0 +1,556 @@
+use crate::benchmark;
+use crate::blnm::Adaptacao;
+use crate::comparacao::Configuracao;
+use crate::config;
+use crate::estatistica::{friedman, resumir, wilcoxon_pareado};
+use crate::instancia::{Ambiente, Distribuicao, Instancia};
+use crate::objetivo::{Objetivo, OBJETIVOS};
+use crate::ui::{App, CampoTexto};
+use crate::utils::{juntar, ler_lista};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+
+const USO_AJUSTE: &str = "uso: task-scheduling-heuristics tune [--algoritmo ils|pareto] \
+[--benchmark <arquivo|diretório>]... [--n <lista>] [--m <lista>] [--ambiente <nome>] \
+[--distribuicao <tipo:parâmetros>] [--objetivo <nome>] [--perturbacoes <lista>] \
+[--adaptacoes <nomes>] [--max-iter <lista>] [--candidatos <k>] [--orcamento <execuções>] [--primeiro-teste <blocos>] \
+[--semente <s>] [--saida <arquivo.conf>] [--silencioso]";
+
+const ALFA: f64 = 0.05;
+
+/// No máximo tantas sobreviventes vão para as listas do arquivo de configuração.
+const MAX_ELITE: usize = 5;
+
+/// Conjunto de treino: instâncias lidas de arquivo, repetidas com outras
+/// sementes do algoritmo, ou uma nova instância gerada a cada bloco.
+enum Treino {
+    Arquivos(Vec<(String, Instancia)>),
+    Gerado {
+        tamanhos: Vec<(usize, usize)>,
+        ambiente: Ambiente,
+        distribuicao: Distribuicao,
+    },
+}
+
+impl Treino {
+    fn instancia(&self, bloco: usize, semente: u64) -> Instancia {
+        match self {
+            Treino::Arquivos(instancias) => instancias[bloco % instancias.len()].1.clone(),
+            Treino::Gerado {
+                tamanhos,
+                ambiente,
+                distribuicao,
+            } => {
+                let (n, m) = tamanhos[bloco % tamanhos.len()];
+                Instancia::gerar(n, m, ambiente, distribuicao, semente)
+            }
+        }
+    }
+
+    fn descrever(&self) -> String {
+        match self {
+            Treino::Arquivos(instancias) => format!("{} instâncias de arquivo", instancias.len()),
+            Treino::Gerado {
+                tamanhos,
+                ambiente,
+                distribuicao,
+            } => {
+                let tamanhos: Vec<String> = tamanhos
+                    .iter()
+                    .map(|(n, m)| format!("n={n} m={m}"))
+                    .collect();
+                format!(
+                    "instâncias geradas ({}; {}; {})",
+                    tamanhos.join(", "),
+                    ambiente.nome(),
+                    distribuicao.nome()
+                )
+            }
+        }
+    }
+}
+
+/// Candidata da corrida com o valor obtido em cada bloco disputado.
+struct Candidata {
+    configuracao: Configuracao,
+    custos: Vec<f64>,
+}
+
+/// Corrida F-Race: a cada bloco (instância e semente), todas as sobreviventes
+/// são executadas; a partir de `primeiro_teste` blocos, se o teste de Friedman
+/// rejeitar a igualdade, saem as candidatas que o Wilcoxon pareado declara
+/// piores que a de menor posto médio. Para quando resta uma candidata ou o
+/// próximo bloco não cabe no orçamento de execuções.
+fn correr(
+    mut candidatas: Vec<Candidata>,
+    treino: &Treino,
+    objetivo: Objetivo,
+    orcamento: usize,
+    primeiro_teste: usize,
+    semente: u64,
+    silencioso: bool,
+) -> io::Result<(Vec<Candidata>, usize)> {
+    let mut stdout = io::stdout().lock();
+    let mut execucoes = 0;
+    let mut bloco = 0;
+    while candidatas.len() > 1 && execucoes + candidatas.len() <= orcamento {
+        let semente_bloco = semente.wrapping_add(bloco as u64);
+        let instancia = treino.instancia(bloco, semente_bloco);
+        for candidata in &mut candidatas {
+            let resultado = candidata.configuracao.executar(
+                &instancia,
+                objetivo,
+                None,
+                semente_bloco,
+                &mut |_| ControlFlow::Continue(()),
+            );
+            candidata.custos.push(resultado.valor_final);
+        }
+        execucoes += candidatas.len();
+        bloco += 1;
+
+        if bloco < primeiro_teste {
+            continue;
+        }
+        let blocos: Vec<Vec<f64>> = (0..bloco)
+            .map(|i| candidatas.iter().map(|c| c.custos[i]).collect())
+            .collect();
+        let (teste, postos) = friedman(&blocos);
+        if teste.p_valor >= ALFA {
+            continue;
+        }
+        let melhor = (0..candidatas.len())
+            .min_by(|&a, &b| postos[a].total_cmp(&postos[b]))
+            .unwrap_or(0);
+        let referencia = candidatas[melhor].custos.clone();
+        let antes = candidatas.len();
+        let mut i = 0;
+        candidatas.retain(|c| {
+            let pior = postos[i] > postos[melhor]
+                && wilcoxon_pareado(&c.custos, &referencia).0.p_valor < ALFA;
+            i += 1;
+            !pior
+        });
+        if !silencioso && candidatas.len() < antes {
+            writeln!(
+                stdout,
+                "bloco {bloco}: Friedman p={:.4}; {antes} → {} candidatas ({execucoes} execuções)",
+                teste.p_valor,
+                candidatas.len()
+            )?;
+        }
+    }
+
+    // Sobreviventes da melhor para a pior pelo posto médio nos blocos disputados
+    let blocos: Vec<Vec<f64>> = (0..bloco)
+        .map(|i| candidatas.iter().map(|c| c.custos[i]).collect())
+        .collect();
+    if candidatas.len() > 1 && bloco > 0 {
+        let (_, postos) = friedman(&blocos);
+        let mut ordenadas: Vec<(f64, Candidata)> = postos.into_iter().zip(candidatas).collect();
+        ordenadas.sort_by(|a, b| a.0.total_cmp(&b.0));
+        candidatas = ordenadas.into_iter().map(|(_, c)| c).collect();
+    }
+    Ok((candidatas, execucoes))
+}
+
+/// Grava as melhores configurações nas listas de perturbação e critério de
+/// parada do arquivo de configuração, com a adaptação da melhor delas,
+/// preservando os demais campos.
+fn gravar_configuracao(elite: &[Configuracao], caminho: &str) -> io::Result<()> {
+    let mut app = App::new();
+    app.arquivo_config = caminho.to_string();
+    config::carregar(&mut app)?;
+
+    let mut perturbacoes = Vec::new();
+    let mut max_iters = Vec::new();
+    for configuracao in elite {
+        if configuracao.algoritmo == 1 && !perturbacoes.contains(&configuracao.perturbacao) {
+            perturbacoes.push(configuracao.perturbacao);
+        }
+        if !max_iters.contains(&configuracao.max_iter) {
+            max_iters.push(configuracao.max_iter);
+        }
+    }
+    let invalido = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+    // A adaptação vem da melhor ILS da elite, a mesma que abre a lista de perturbações
+    if let Some(melhor_ils) = elite.iter().find(|c| c.algoritmo == 1) {
+        app.definir_campo(CampoTexto::Perturbacoes, &juntar(&perturbacoes))
+            .map_err(invalido)?;
+        app.definir_campo(CampoTexto::Adaptacao, melhor_ils.adaptacao.nome())
+            .map_err(invalido)?;
+    }
+    app.definir_campo(CampoTexto::MaxIteracoes, &juntar(&max_iters))
+        .map_err(invalido)?;
+    config::salvar(
+        &mut app,
+        &[
+            CampoTexto::Perturbacoes,
+            CampoTexto::Adaptacao,
+            CampoTexto::MaxIteracoes,
+        ],
+    )
+}
+
+/// Modo `tune`: ajuste dos parâmetros da ILS (perturbação e iterações sem
+/// melhora) ou da busca de Pareto (máximo de explorações) por corrida.
+pub fn executar(args: &[String]) -> io::Result<()> {
+    let invalido =
+        |msg: String| io::Error::new(io::ErrorKind::InvalidInput, format!("{msg}\n{USO_AJUSTE}"));
+    let mut algoritmo = 1;
+    let mut benchmarks = Vec::new();
+    let mut n_values = vec![100];
+    let mut m_values = vec![10];
+    let mut ambiente = Ambiente::padroes().remove(0);
+    let mut distribuicao = Distribuicao::PADRAO;
+    let mut objetivo = OBJETIVOS[0];
+    let mut perturbacoes = None;
+    let mut adaptacoes = None;
+    let mut max_iters = None;
+    let mut n_candidatas = None;
+    let mut orcamento = 500;
+    let mut primeiro_teste = 6;
+    let mut semente: u64 = rand::random();
+    let mut saida = config::ARQUIVO_PADRAO.to_string();
+    let mut silencioso = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut valor = |opcao: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| invalido(format!("{opcao} exige um valor")))
+        };
+        // Listas de inteiros positivos (n, m) ou, com `minimo` 0, não negativos
+        let inteiros = |texto: &str, minimo: f64| {
+            ler_lista(texto)
+                .filter(|v| v.iter().all(|&x| x >= minimo && x.fract() == 0.0))
+                .ok_or_else(|| invalido(format!("lista inválida: {texto}")))
+        };
+        let numero = |texto: &str| {
+            texto
+                .parse::<usize>()
+                .ok()
+                .filter(|&k| k > 0)
+                .ok_or_else(|| invalido(format!("esperado um inteiro positivo: {texto}")))
+        };
+        match arg.as_str() {
+            "--algoritmo" => {
+                algoritmo = match valor(arg)?.as_str() {
+                    "ils" => 1,
+                    "pareto" => 2,
+                    outro => {
+                        return Err(invalido(format!(
+                            "algoritmo sem parâmetros a ajustar: {outro} (use ils ou pareto)"
+                        )))
+                    }
+                }
+            }
+            "--benchmark" => benchmarks.extend(benchmark::carregar(&valor(arg)?)?),
+            "--n" => {
+                n_values = inteiros(&valor(arg)?, 1.0)?
+                    .into_iter()
+                    .map(|n| n as usize)
+                    .collect()
+            }
+            "--m" => {
+                m_values = inteiros(&valor(arg)?, 1.0)?
+                    .into_iter()
+                    .map(|m| m as usize)
+                    .collect()
+            }
+            "--ambiente" => {
+                let texto = valor(arg)?;
+                ambiente = Ambiente::padroes()
+                    .into_iter()
+                    .find(|a| a.nome() == texto)
+                    .ok_or_else(|| {
+                        let nomes: Vec<String> =
+                            Ambiente::padroes().iter().map(Ambiente::nome).collect();
+                        invalido(format!(
+                            "ambiente desconhecido: {texto} (use {})",
+                            nomes.join(", ")
+                        ))
+                    })?;
+            }
+            "--distribuicao" => {
+                let texto = valor(arg)?;
+                distribuicao = Distribuicao::ler(&texto)
+                    .ok_or_else(|| invalido(format!("distribuição inválida: {texto}")))?;
+            }
+            "--objetivo" => {
+                let texto = valor(arg)?;
+                objetivo = Objetivo::ler(&texto)
+                    .ok_or_else(|| invalido(format!("objetivo inválido: {texto}")))?;
+            }
+            "--perturbacoes" => {
+                let texto = valor(arg)?;
+                perturbacoes = Some(
+                    ler_lista(&texto)
+                        .filter(|v| v.iter().all(|&p| p > 0.0 && p <= 1.0))
+                        .ok_or_else(|| {
+                            invalido(format!("perturbações devem estar em (0, 1]: {texto}"))
+                        })?,
+                );
+            }
+            "--adaptacoes" => {
+                let texto = valor(arg)?;
+                adaptacoes = Some(
+                    texto
+                        .split(',')
+                        .map(|nome| Adaptacao::ler(nome.trim()))
+                        .collect::<Option<Vec<Adaptacao>>>()
+                        .ok_or_else(|| {
+                            invalido(format!(
+                                "adaptações devem ser fixa, escalonada ou reativa: {texto}"
+                            ))
+                        })?,
+                );
+            }
+            "--max-iter" => {
+                max_iters = Some(
+                    inteiros(&valor(arg)?, 0.0)?
+                        .into_iter()
+                        .map(|v| v.min(u32::MAX as f64) as u32)
+                        .collect::<Vec<u32>>(),
+                )
+            }
+            "--candidatos" => n_candidatas = Some(numero(&valor(arg)?)?),
+            "--orcamento" => orcamento = numero(&valor(arg)?)?,
+            "--primeiro-teste" => primeiro_teste = numero(&valor(arg)?)?,
+            "--semente" => {
+                let texto = valor(arg)?;
+                semente = texto
+                    .parse()
+                    .map_err(|_| invalido(format!("semente inválida: {texto}")))?;
+            }
+            "--saida" | "-o" => saida = valor(arg)?,
+            "--silencioso" => silencioso = true,
+            "--help" | "-h" => return Err(invalido(String::new())),
+            outro => return Err(invalido(format!("opção desconhecida: {outro}"))),
+        }
+    }
+    if algoritmo == 2 && (perturbacoes.is_some() || adaptacoes.is_some()) {
+        return Err(invalido(
+            "a busca de Pareto não tem perturbação a ajustar".to_string(),
+        ));
+    }
+
+    // Candidatas: a grade completa, ou uma amostra aleatória dela com --candidatos
+    let perturbacoes = match algoritmo {
+        1 => perturbacoes.unwrap_or_else(|| vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9]),
+        _ => vec![0.0],
+    };
+    let adaptacoes = adaptacoes.unwrap_or_else(|| vec![Adaptacao::Fixa]);
+    let max_iters = max_iters.unwrap_or_else(|| vec![100, 500, 1000, 2000]);
+    let mut grade = Vec::new();
+    for &adaptacao in &adaptacoes {
+        for &perturbacao in &perturbacoes {
+            for &max_iter in &max_iters {
+                grade.push(Configuracao {
+                    algoritmo,
+                    perturbacao,
+                    adaptacao,
+                    max_iter,
+                });
+            }
+        }
+    }
+    if let Some(k) = n_candidatas.filter(|&k| k < grade.len()) {
+        grade.shuffle(&mut StdRng::seed_from_u64(semente));
+        grade.truncate(k);
+    }
+    if grade.len() < 2 {
+        return Err(invalido(
+            "são necessárias ao menos duas candidatas".to_string(),
+        ));
+    }
+
+    let treino = if benchmarks.is_empty() {
+        Treino::Gerado {
+            tamanhos: n_values
+                .iter()
+                .flat_map(|&n| m_values.iter().map(move |&m| (n, m)))
+                .collect(),
+            ambiente,
+            distribuicao,
+        }
+    } else {
+        Treino::Arquivos(benchmarks)
+    };
+
+    if !silencioso {
+        println!(
+            "Corrida: {} candidatas, orçamento de {orcamento} execuções, {}, objetivo {}, semente {semente}",
+            grade.len(),
+            treino.descrever(),
+            objetivo.nome()
+        );
+    }
+    let candidatas = grade
+        .into_iter()
+        .map(|configuracao| Candidata {
+            configuracao,
+            custos: Vec::new(),
+        })
+        .collect();
+    let (sobreviventes, execucoes) = correr(
+        candidatas,
+        &treino,
+        objetivo,
+        orcamento,
+        primeiro_teste,
+        semente,
+        silencioso,
+    )?;
+    if sobreviventes.first().is_none_or(|c| c.custos.is_empty()) {
+        return Err(invalido(format!(
+            "orçamento de {orcamento} execuções não cobre um bloco com todas as candidatas"
+        )));
+    }
+
+    println!(
+        "{} sobreviventes após {} blocos e {execucoes} execuções:",
+        sobreviventes.len(),
+        sobreviventes[0].custos.len()
+    );
+    for (i, candidata) in sobreviventes.iter().enumerate() {
+        println!(
+            "{:>3}. {:<30} média {:.2}",
+            i + 1,
+            candidata.configuracao.nome(),
+            resumir(&candidata.custos).media
+        );
+    }
+
+    let elite: Vec<Configuracao> = sobreviventes
+        .iter()
+        .take(MAX_ELITE)
+        .map(|c| c.configuracao)
+        .collect();
+    gravar_configuracao(&elite, &saida)?;
+    println!("Configuração ajustada gravada em: {saida}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ArquivoTemporario;
+
+    const BLM: Configuracao = Configuracao {
+        algoritmo: 0,
+        perturbacao: 0.0,
+        adaptacao: Adaptacao::Fixa,
+        max_iter: 0,
+    };
+    const ILS: Configuracao = Configuracao {
+        algoritmo: 1,
+        perturbacao: 0.2,
+        adaptacao: Adaptacao::Fixa,
+        max_iter: 30,
+    };
+
+    fn candidatas(configuracoes: &[Configuracao]) -> Vec<Candidata> {
+        configuracoes
+            .iter()
+            .map(|&configuracao| Candidata {
+                configuracao,
+                custos: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn treino() -> Treino {
+        Treino::Gerado {
+            tamanhos: vec![(20, 4)],
+            ambiente: Ambiente::padroes().remove(0),
+            distribuicao: Distribuicao::PADRAO,
+        }
+    }
+
+    fn nomes(candidatas: &[Candidata]) -> Vec<String> {
+        candidatas.iter().map(|c| c.configuracao.nome()).collect()
+    }
+
+    #[test]
+    fn corrida_elimina_as_piores_que_a_de_menor_posto() {
+        // A ILS parte da mesma solução da BLM com a mesma semente e só melhora;
+        // as duas ILS idênticas empatam entre si e sobrevivem juntas
+        let (sobreviventes, execucoes) = correr(
+            candidatas(&[BLM, ILS, ILS]),
+            &treino(),
+            OBJETIVOS[0],
+            60,
+            6,
+            3,
+            true,
+        )
+        .unwrap();
+        assert_eq!(nomes(&sobreviventes), [ILS.nome(), ILS.nome()]);
+        assert_eq!(sobreviventes[0].custos, sobreviventes[1].custos);
+        // Depois, as duas seguem até o próximo bloco não caber no orçamento
+        assert!((59..=60).contains(&execucoes), "{execucoes}");
+    }
+
+    #[test]
+    fn sem_teste_antes_do_primeiro_bloco_e_parada_pelo_orcamento() {
+        // Com 2 candidatas, o quinto bloco passaria do orçamento de 9 execuções
+        let (sobreviventes, execucoes) = correr(
+            candidatas(&[BLM, ILS]),
+            &treino(),
+            OBJETIVOS[0],
+            9,
+            6,
+            3,
+            true,
+        )
+        .unwrap();
+        assert_eq!(execucoes, 8);
+        assert_eq!(nomes(&sobreviventes), [ILS.nome(), BLM.nome()]);
+        assert!(sobreviventes.iter().all(|c| c.custos.len() == 4));
+        let (ils, blm) = (&sobreviventes[0].custos, &sobreviventes[1].custos);
+        assert!(ils.iter().zip(blm).all(|(i, b)| i <= b));
+    }
+
+    #[test]
+    fn gravar_configuracao_usa_a_adaptacao_da_melhor_ils() {
+        let arquivo = ArquivoTemporario::com("ajuste.conf", "execucoes = 7\nadaptacao = fixa\n");
+        let ils = |perturbacao, adaptacao, max_iter| Configuracao {
+            algoritmo: 1,
+            perturbacao,
+            adaptacao,
+            max_iter,
+        };
+        let pls = Configuracao {
+            algoritmo: 2,
+            max_iter: 50,
+            ..BLM
+        };
+        let elite = [
+            pls,
+            ils(0.3, Adaptacao::Reativa, 100),
+            ils(0.1, Adaptacao::Escalonada, 200),
+            ils(0.3, Adaptacao::Fixa, 100),
+        ];
+        gravar_configuracao(&elite, &arquivo.texto()).unwrap();
+
+        let mut app = App::new();
+        app.arquivo_config = arquivo.texto();
+        config::carregar(&mut app).unwrap();
+        assert_eq!(app.texto_campo(CampoTexto::Perturbacoes), "0.3,0.1");
+        assert_eq!(app.texto_campo(CampoTexto::Adaptacao), "reativa");
+        assert_eq!(app.texto_campo(CampoTexto::MaxIteracoes), "50,100,200");
+        assert_eq!(app.texto_campo(CampoTexto::Execucoes), "7");
+
+        // Sem ILS na elite, perturbação e adaptação ficam como estavam
+        gravar_configuracao(&[pls], &arquivo.texto()).unwrap();
+        let mut app = App::new();
+        app.arquivo_config = arquivo.texto();
+        config::carregar(&mut app).unwrap();
+        assert_eq!(app.texto_campo(CampoTexto::Perturbacoes), "0.3,0.1");
+        assert_eq!(app.texto_campo(CampoTexto::Adaptacao), "reativa");
+        assert_eq!(app.texto_campo(CampoTexto::MaxIteracoes), "50");
+    }
+}
@@ -0,0 +1,471 @@
+use crate::estatistica::{
+    diferenca_critica_nemenyi, friedman, mann_whitney, resumir, wilcoxon_pareado,
+};
+use std::fs::File;
+use std::io::{self, Write};
+
+const ALFA: f64 = 0.05;
+
+/// Valores finais de uma variante de algoritmo (ver [`sigla`]) em uma classe (n, m).
+struct Amostra {
+    algoritmo: String,
+    valores: Vec<f64>,
+    /// Bloco de cada execução para os testes pareados: a semente da replicação,
+    /// que também gera a instância, e quantas vezes ela já apareceu na amostra.
+    /// `None` quando o CSV não registra a semente.
+    blocos: Vec<Option<(String, usize)>>,
+}
+
+impl Amostra {
+    fn adicionar(&mut self, valor: f64, semente: Option<&str>) {
+        let bloco = semente.map(|s| {
+            let repeticoes = self
+                .blocos
+                .iter()
+                .flatten()
+                .filter(|(outra, _)| outra == s)
+                .count();
+            (s.to_string(), repeticoes)
+        });
+        self.valores.push(valor);
+        self.blocos.push(bloco);
+    }
+}
+
+/// Valores das amostras nos blocos presentes em todas elas, na ordem da
+/// primeira: `blocos[i][j]` é o valor da amostra j no i-ésimo bloco comum.
+fn blocos_comuns(amostras: &[&Amostra]) -> Vec<Vec<f64>> {
+    let primeira = amostras[0];
+    primeira
+        .blocos
+        .iter()
+        .zip(&primeira.valores)
+        .filter_map(|(bloco, &valor)| {
+            let bloco = bloco.as_ref()?;
+            let mut linha = vec![valor];
+            for outra in &amostras[1..] {
+                let j = outra
+                    .blocos
+                    .iter()
+                    .position(|b| b.as_ref() == Some(bloco))?;
+                linha.push(outra.valores[j]);
+            }
+            Some(linha)
+        })
+        .collect()
+}
+
+struct Grupo {
+    n: usize,
+    m: usize,
+    ambiente: String,
+    objetivo: String,
+    amostras: Vec<Amostra>,
+}
+
+pub struct LinhaAnalise {
+    pub n: usize,
+    pub m: usize,
+    pub ambiente: String,
+    pub objetivo: String,
+    pub teste: &'static str,
+    pub algoritmo_a: String,
+    pub algoritmo_b: String,
+    pub estatistica: f64,
+    pub p_valor: Option<f64>,
+    pub efeito: Option<f64>,
+    pub medida_efeito: &'static str,
+    pub significativo: bool,
+}
+
+/// Sigla da heurística gravada no CSV de resultados.
+pub fn nome_curto(heuristica: &str) -> &str {
+    match heuristica {
+        "busca-local-monotona-melhorada" => "BLM",
+        "busca-local-iterada" => "ILS",
+        "busca-local-iterada-escalonada" => "ILS-E",
+        "busca-local-iterada-reativa" => "ILS-R",
+        "busca-local-pareto" => "PLS",
+        outro => outro,
+    }
+}
+
+/// Variante de algoritmo: a heurística (cujo nome já distingue a adaptação
+/// da perturbação), a perturbação e o critério de parada.
+fn sigla(heuristica: &str, parametro: &str, max_iter: &str) -> String {
+    let nome = nome_curto(heuristica);
+    let informado = |v: &str| !v.is_empty() && v != "NA";
+    match (informado(parametro), informado(max_iter)) {
+        (true, true) => format!("{nome}(p={parametro}, {max_iter})"),
+        (true, false) => format!("{nome}(p={parametro})"),
+        (false, true) => format!("{nome}({max_iter})"),
+        (false, false) => nome.to_string(),
+    }
+}
+
+/// Lê o CSV de resultados e agrupa por (n, m, ambiente, objetivo); dentro de
+/// cada grupo, cada combinação (heurística, parâmetro, max_iter) é tratada como um
+/// algoritmo distinto. Antes do esquema v3 todas as máquinas eram idênticas,
+/// e antes do v4 o objetivo era sempre o makespan. A partir do v6, instâncias
+/// de benchmark distintas ficam em grupos separados, com o nome da instância
+/// junto ao ambiente.
+fn carregar_grupos(caminho: &str) -> io::Result<Vec<Grupo>> {
+    let mut leitor = csv::Reader::from_path(caminho)?;
+    let cabecalho = leitor.headers()?.clone();
+    let coluna = |nome: &str| {
+        cabecalho.iter().position(|c| c == nome).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("coluna '{nome}' ausente em {caminho}"),
+            )
+        })
+    };
+    let (c_heur, c_n, c_m, c_valor, c_param) = (
+        coluna("heuristica")?,
+        coluna("n")?,
+        coluna("m")?,
+        coluna("valor")?,
+        coluna("parametro")?,
+    );
+    let c_amb = coluna("ambiente").ok();
+    let c_obj = coluna("objetivo").ok();
+    let c_inst = coluna("instancia").ok();
+    let c_semente = coluna("semente").ok();
+    let c_max_iter = coluna("max_iter").ok();
+
+    let invalido = |linha: usize, campo: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("linha {linha}: valor inválido na coluna '{campo}'"),
+        )
+    };
+
+    let mut grupos: Vec<Grupo> = Vec::new();
+    for (i, registro) in leitor.records().enumerate() {
+        let registro = registro?;
+        let linha = i + 2;
+        let n: usize = registro[c_n].parse().map_err(|_| invalido(linha, "n"))?;
+        let m: usize = registro[c_m].parse().map_err(|_| invalido(linha, "m"))?;
+        let valor: f64 = registro[c_valor]
+            .parse()
+            .map_err(|_| invalido(linha, "valor"))?;
+        let algoritmo = sigla(
+            &registro[c_heur],
+            &registro[c_param],
+            c_max_iter.map_or("NA", |c| &registro[c]),
+        );
+        let mut ambiente = match c_amb.map(|c| &registro[c]) {
+            None | Some("NA") => "identicas".to_string(),
+            Some(v) => v.to_string(),
+        };
+        if let Some(instancia) = c_inst.map(|c| &registro[c]).filter(|&v| v != "NA") {
+            ambiente = format!("{ambiente}/{instancia}");
+        }
+        let objetivo = match c_obj.map(|c| &registro[c]) {
+            None | Some("NA") => "makespan".to_string(),
+            Some(v) => v.to_string(),
+        };
+
+        let grupo = match grupos
+            .iter_mut()
+            .position(|g| g.n == n && g.m == m && g.ambiente == ambiente && g.objetivo == objetivo)
+        {
+            Some(pos) => &mut grupos[pos],
+            None => {
+                grupos.push(Grupo {
+                    n,
+                    m,
+                    ambiente,
+                    objetivo,
+                    amostras: Vec::new(),
+                });
+                grupos.last_mut().unwrap()
+            }
+        };
+        let semente = c_semente.map(|c| &registro[c]).filter(|&s| s != "NA");
+        let amostra = match grupo.amostras.iter().position(|a| a.algoritmo == algoritmo) {
+            Some(pos) => &mut grupo.amostras[pos],
+            None => {
+                grupo.amostras.push(Amostra {
+                    algoritmo,
+                    valores: Vec::new(),
+                    blocos: Vec::new(),
+                });
+                grupo.amostras.last_mut().unwrap()
+            }
+        };
+        amostra.adicionar(valor, semente);
+    }
+
+    Ok(grupos)
+}
+
+fn analisar_grupo(grupo: &Grupo) -> Vec<LinhaAnalise> {
+    let mut linhas = Vec::new();
+    let linha =
+        |teste, a: &str, b: &str, estatistica, p_valor: Option<f64>, efeito, medida| LinhaAnalise {
+            n: grupo.n,
+            m: grupo.m,
+            ambiente: grupo.ambiente.clone(),
+            objetivo: grupo.objetivo.clone(),
+            teste,
+            algoritmo_a: a.to_string(),
+            algoritmo_b: b.to_string(),
+            estatistica,
+            p_valor,
+            efeito,
+            medida_efeito: medida,
+            significativo: p_valor.is_some_and(|p| p < ALFA),
+        };
+
+    // Comparações par a par; o Wilcoxon só pareia execuções da mesma semente,
+    // isto é, sobre a mesma instância, e fica de fora se não houver nenhuma
+    for (i, a) in grupo.amostras.iter().enumerate() {
+        for b in &grupo.amostras[i + 1..] {
+            let pares = blocos_comuns(&[a, b]);
+            if !pares.is_empty() {
+                let (valores_a, valores_b): (Vec<f64>, Vec<f64>) =
+                    pares.iter().map(|p| (p[0], p[1])).unzip();
+                let (teste, rb) = wilcoxon_pareado(&valores_a, &valores_b);
+                linhas.push(linha(
+                    "wilcoxon",
+                    &a.algoritmo,
+                    &b.algoritmo,
+                    teste.estatistica,
+                    Some(teste.p_valor),
+                    Some(rb),
+                    "rank-biserial",
+                ));
+            }
+            let (teste, a12) = mann_whitney(&a.valores, &b.valores);
+            linhas.push(linha(
+                "mann-whitney",
+                &a.algoritmo,
+                &b.algoritmo,
+                teste.estatistica,
+                Some(teste.p_valor),
+                Some(a12),
+                "A12",
+            ));
+        }
+    }
+
+    // Friedman com blocos = sementes executadas por todos os algoritmos
+    let k = grupo.amostras.len();
+    let blocos = if k >= 3 {
+        blocos_comuns(&grupo.amostras.iter().collect::<Vec<_>>())
+    } else {
+        Vec::new()
+    };
+    let n_blocos = blocos.len();
+    if n_blocos > 0 {
+        let (teste, postos_medios) = friedman(&blocos);
+        let kendall_w = teste.estatistica / (n_blocos as f64 * (k as f64 - 1.0));
+        linhas.push(linha(
+            "friedman",
+            "todos",
+            "",
+            teste.estatistica,
+            Some(teste.p_valor),
+            Some(kendall_w),
+            "W-kendall",
+        ));
+        for (amostra, posto) in grupo.amostras.iter().zip(&postos_medios) {
+            linhas.push(linha(
+                "posto-medio",
+                &amostra.algoritmo,
+                "",
+                *posto,
+                None,
+                None,
+                "",
+            ));
+        }
+        if let Some(cd) = diferenca_critica_nemenyi(k, n_blocos) {
+            for i in 0..k {
+                for j in (i + 1)..k {
+                    let diferenca = (postos_medios[i] - postos_medios[j]).abs();
+                    let mut nemenyi = linha(
+                        "nemenyi",
+                        &grupo.amostras[i].algoritmo,
+                        &grupo.amostras[j].algoritmo,
+                        diferenca,
+                        None,
+                        Some(cd),
+                        "diferenca-critica",
+                    );
+                    nemenyi.significativo = diferenca > cd;
+                    linhas.push(nemenyi);
+                }
+            }
+        }
+    }
+
+    linhas
+}
+
+fn formatar_opcional(valor: Option<f64>) -> String {
+    valor.map_or("NA".to_string(), |v| format!("{v:.4}"))
+}
+
+fn imprimir_grupo(grupo: &Grupo, linhas: &[LinhaAnalise]) {
+    println!(
+        "=== n={} m={} ambiente={} objetivo={} ===",
+        grupo.n, grupo.m, grupo.ambiente, grupo.objetivo
+    );
+    println!(
+        "{:<24}{:>8}{:>12}{:>12}{:>12}",
+        "Algoritmo", "execs", "média", "mediana", "desvio"
+    );
+    for amostra in &grupo.amostras {
+        let resumo = resumir(&amostra.valores);
+        println!(
+            "{:<24}{:>8}{:>12.2}{:>12.2}{:>12.2}",
+            amostra.algoritmo,
+            amostra.valores.len(),
+            resumo.media,
+            resumo.mediana,
+            resumo.desvio
+        );
+    }
+    println!();
+    println!(
+        "{:<16}{:<36}{:>12}{:>10}{:>12}  {:<18}",
+        "Teste", "Comparação", "Estatística", "p-valor", "Efeito", "Medida"
+    );
+    for l in linhas {
+        let comparacao = if l.algoritmo_b.is_empty() {
+            l.algoritmo_a.clone()
+        } else {
+            format!("{} x {}", l.algoritmo_a, l.algoritmo_b)
+        };
+        println!(
+            "{:<16}{:<36}{:>12.4}{:>10}{:>12}  {:<18}{}",
+            l.teste,
+            comparacao,
+            l.estatistica,
+            formatar_opcional(l.p_valor),
+            formatar_opcional(l.efeito),
+            l.medida_efeito,
+            if l.significativo { " *" } else { "" }
+        );
+    }
+    println!();
+}
+
+pub fn salvar_analise_csv(linhas: &[LinhaAnalise], caminho: &str) -> io::Result<()> {
+    let mut file = File::create(caminho)?;
+    writeln!(
+        file,
+        "teste,n,m,ambiente,objetivo,algoritmo_a,algoritmo_b,estatistica,p_valor,efeito,medida_efeito,significativo"
+    )?;
+    for l in linhas {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{},{:.6},{},{},{},{}",
+            l.teste,
+            l.n,
+            l.m,
+            l.ambiente,
+            l.objetivo,
+            l.algoritmo_a,
+            l.algoritmo_b,
+            l.estatistica,
+            formatar_opcional(l.p_valor),
+            formatar_opcional(l.efeito),
+            l.medida_efeito,
+            l.significativo
+        )?;
+    }
+    Ok(())
+}
+
+/// Modo `analyze <resultados.csv> [--saida <analise.csv>]`.
+pub fn executar(args: &[String]) -> io::Result<()> {
+    const USO_ANALISE: &str = "uso: analyze <resultados.csv> [--saida <analise.csv>]";
+    let invalido =
+        |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, format!("{msg}{USO_ANALISE}"));
+    let mut entrada = None;
+    let mut saida = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--saida" | "-o" => {
+                saida = Some(
+                    iter.next()
+                        .cloned()
+                        .ok_or_else(|| invalido(&format!("{arg} exige um valor\n")))?,
+                )
+            }
+            _ => entrada = Some(arg.clone()),
+        }
+    }
+    let entrada = entrada.ok_or_else(|| invalido(""))?;
+
+    let grupos = carregar_grupos(&entrada)?;
+    let mut todas = Vec::new();
+    for grupo in &grupos {
+        let linhas = analisar_grupo(grupo);
+        imprimir_grupo(grupo, &linhas);
+        todas.extend(linhas);
+    }
+    println!("* significativo com α = {ALFA}");
+
+    if let Some(saida) = saida {
+        salvar_analise_csv(&todas, &saida)?;
+        println!("Análise salva em: {saida}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amostra(execucoes: &[(f64, &str)]) -> Amostra {
+        let mut amostra = Amostra {
+            algoritmo: String::new(),
+            valores: Vec::new(),
+            blocos: Vec::new(),
+        };
+        for &(valor, semente) in execucoes {
+            amostra.adicionar(valor, Some(semente).filter(|&s| s != "NA"));
+        }
+        amostra
+    }
+
+    #[test]
+    fn blocos_pareados_pela_semente() {
+        let a = amostra(&[(1.0, "7"), (2.0, "8"), (3.0, "9"), (4.0, "7")]);
+        let b = amostra(&[(30.0, "9"), (10.0, "7"), (50.0, "5"), (11.0, "7")]);
+        assert_eq!(
+            blocos_comuns(&[&a, &b]),
+            vec![vec![1.0, 10.0], vec![3.0, 30.0], vec![4.0, 11.0]]
+        );
+    }
+
+    #[test]
+    fn sigla_separa_parametros_e_adaptacao() {
+        assert_eq!(
+            sigla("busca-local-iterada", "0.3", "100"),
+            "ILS(p=0.3, 100)"
+        );
+        assert_ne!(
+            sigla("busca-local-iterada", "0.3", "100"),
+            sigla("busca-local-iterada", "0.3", "500")
+        );
+        assert_eq!(
+            sigla("busca-local-iterada-reativa", "0.3", "100"),
+            "ILS-R(p=0.3, 100)"
+        );
+        assert_eq!(sigla("busca-local-pareto", "NA", "50"), "PLS(50)");
+        assert_eq!(sigla("busca-local-monotona-melhorada", "NA", "NA"), "BLM");
+    }
+
+    #[test]
+    fn sem_semente_nao_ha_blocos() {
+        let a = amostra(&[(1.0, "NA"), (2.0, "NA")]);
+        let b = amostra(&[(1.0, "NA"), (2.0, "NA")]);
+        assert!(blocos_comuns(&[&a, &b]).is_empty());
+    }
+}
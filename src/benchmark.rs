@@ -0,0 +1,151 @@
+use crate::instancia::{Instancia, Referencia};
+use std::io;
+use std::path::Path;
+
+/// Melhor makespan conhecido de uma instância de benchmark.
+pub struct EntradaCatalogo {
+    pub instancia: String,
+    pub makespan: f64,
+    pub otimo: bool,
+}
+
+/// Nome da instância no catálogo: o nome do arquivo sem diretório nem extensão.
+pub fn nome_instancia(caminho: &str) -> String {
+    Path::new(caminho)
+        .file_stem()
+        .map_or(caminho.to_string(), |s| s.to_string_lossy().into_owned())
+}
+
+/// Lê o catálogo de melhores conhecidos: CSV com cabeçalho
+/// `instancia,makespan[,otimo]`, em que `otimo` (sim/não, true/false ou 1/0)
+/// indica se o valor foi provado ótimo.
+pub fn ler_catalogo(caminho: &str) -> io::Result<Vec<EntradaCatalogo>> {
+    let invalido =
+        |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{caminho}: {msg}"));
+    let mut leitor = csv::Reader::from_path(caminho)?;
+    let cabecalho = leitor.headers()?.clone();
+    let coluna = |nome: &str| cabecalho.iter().position(|c| c.trim() == nome);
+    let (Some(c_instancia), Some(c_makespan)) = (coluna("instancia"), coluna("makespan")) else {
+        return Err(invalido(
+            "o cabeçalho deve conter instancia e makespan".to_string(),
+        ));
+    };
+    let c_otimo = coluna("otimo");
+
+    leitor
+        .records()
+        .map(|registro| {
+            let registro = registro?;
+            let campo = |c: usize| registro.get(c).unwrap_or("").trim();
+            let makespan = campo(c_makespan)
+                .parse()
+                .map_err(|_| invalido(format!("makespan inválido: {}", campo(c_makespan))))?;
+            let otimo = match c_otimo.map(campo) {
+                None | Some("" | "não" | "nao" | "false" | "0") => false,
+                Some("sim" | "true" | "1") => true,
+                Some(outro) => return Err(invalido(format!("valor de otimo inválido: {outro}"))),
+            };
+            Ok(EntradaCatalogo {
+                instancia: campo(c_instancia).to_string(),
+                makespan,
+                otimo,
+            })
+        })
+        .collect()
+}
+
+/// Lê uma instância de benchmark, ou todas as de um diretório em ordem
+/// alfabética, já identificadas pelo nome do arquivo.
+pub fn carregar(caminho: &str) -> io::Result<Vec<(String, Instancia)>> {
+    let mut arquivos = Vec::new();
+    if Path::new(caminho).is_dir() {
+        for entrada in std::fs::read_dir(caminho)? {
+            let entrada = entrada?.path();
+            if entrada.is_file() {
+                arquivos.push(entrada.to_string_lossy().into_owned());
+            }
+        }
+        arquivos.sort();
+    } else {
+        arquivos.push(caminho.to_string());
+    }
+
+    arquivos
+        .into_iter()
+        .map(|arquivo| {
+            let mut instancia = Instancia::ler_benchmark(&arquivo)?;
+            instancia.referencia = Some(Referencia {
+                nome: nome_instancia(&arquivo),
+                melhor_conhecido: None,
+                otimo: false,
+            });
+            Ok((arquivo, instancia))
+        })
+        .collect()
+}
+
+/// Associa a cada instância o melhor conhecido registrado no catálogo.
+pub fn aplicar_catalogo(instancias: &mut [(String, Instancia)], catalogo: &[EntradaCatalogo]) {
+    for (caminho, instancia) in instancias {
+        let nome = nome_instancia(caminho);
+        let entrada = catalogo.iter().find(|e| e.instancia == nome);
+        instancia.referencia = Some(Referencia {
+            melhor_conhecido: entrada.map(|e| e.makespan),
+            otimo: entrada.is_some_and(|e| e.otimo),
+            nome,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ArquivoTemporario;
+
+    fn ler(nome: &str, conteudo: &str) -> io::Result<Vec<EntradaCatalogo>> {
+        let arquivo = ArquivoTemporario::com(&format!("{nome}.csv"), conteudo);
+        ler_catalogo(&arquivo.texto())
+    }
+
+    #[test]
+    fn catalogo_valido() {
+        let catalogo = ler(
+            "catalogo",
+            "makespan,instancia,otimo\n120,ta01,sim\n98.5, ta02 ,0\n77,ta03,\n",
+        )
+        .unwrap();
+        let lidos: Vec<_> = catalogo
+            .iter()
+            .map(|e| (e.instancia.as_str(), e.makespan, e.otimo))
+            .collect();
+        assert_eq!(
+            lidos,
+            [
+                ("ta01", 120.0, true),
+                ("ta02", 98.5, false),
+                ("ta03", 77.0, false)
+            ]
+        );
+
+        let sem_otimo = ler("catalogo-sem-otimo", "instancia,makespan\nta01,5\n").unwrap();
+        assert!(!sem_otimo[0].otimo);
+    }
+
+    #[test]
+    fn catalogo_invalido() {
+        for conteudo in [
+            "instancia,melhor\nta01,5\n",
+            "instancia,makespan\nta01,cinco\n",
+            "instancia,makespan,otimo\nta01,5,talvez\n",
+            "instancia,makespan\nta01,5,6\n",
+        ] {
+            assert!(ler("catalogo-invalido", conteudo).is_err(), "{conteudo:?}");
+        }
+    }
+
+    #[test]
+    fn nome_sem_diretorio_nem_extensao() {
+        assert_eq!(nome_instancia("dados/lawrence/la01.txt"), "la01");
+        assert_eq!(nome_instancia("ta01"), "ta01");
+    }
+}
@@ -1,49 +1,185 @@
-use crate::utils::Result;
-use rand::Rng;
+use crate::instancia::Instancia;
+use crate::objetivo::{Custo, Objetivo};
+use crate::utils::{PontoConvergencia, Result, TarefaAlocada};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::ops::ControlFlow;
 use std::time::Instant;
 
-pub struct Maquina {
-    pub tarefas: Vec<u32>,
+#[derive(Clone)]
+pub struct Maquina<'a> {
+    /// Índices das tarefas alocadas, na ordem de processamento.
+    pub tarefas: Vec<usize>,
     pub pos: i32,
+    pub indice: usize,
+    pub instancia: &'a Instancia,
 }
 
-impl Maquina {
-    pub fn new(n: usize) -> Self {
+impl<'a> Maquina<'a> {
+    pub fn new(instancia: &'a Instancia, indice: usize) -> Self {
         Maquina {
-            tarefas: vec![0; n],
+            tarefas: vec![0; instancia.n_tarefas()],
             pos: -1,
+            indice,
+            instancia,
         }
     }
 
-    pub fn ms_maquina(&self) -> u32 {
+    /// Tarefas atualmente alocadas à máquina.
+    pub fn ativas(&self) -> &[usize] {
         if self.pos < 0 {
-            return 0;
+            return &[];
         }
-        self.tarefas[0..=(self.pos as usize)].iter().sum()
+        &self.tarefas[0..=(self.pos as usize)]
+    }
+
+    /// Se a tarefa pode ser alocada nesta máquina.
+    pub fn aceita(&self, tarefa: usize) -> bool {
+        self.instancia.elegivel(tarefa, self.indice)
+    }
+
+    /// Tempo para esta máquina processar a tarefa.
+    pub fn duracao(&self, tarefa: usize) -> f64 {
+        self.instancia.duracao(tarefa, self.indice)
+    }
+
+    /// Acrescenta a tarefa ao fim da máquina.
+    pub fn adicionar(&mut self, tarefa: usize) {
+        self.pos += 1;
+        let pos = self.pos as usize;
+        self.tarefas[pos] = tarefa;
+    }
+
+    /// Insere a tarefa na posição `pos`, deslocando as seguintes.
+    pub fn inserir(&mut self, pos: usize, tarefa: usize) {
+        self.tarefas.pop();
+        self.tarefas.insert(pos, tarefa);
+        self.pos += 1;
+    }
+
+    /// Substitui as tarefas da máquina pela sequência dada.
+    pub fn definir(&mut self, sequencia: &[usize]) {
+        let n = self.tarefas.len();
+        self.tarefas.clear();
+        self.tarefas.extend_from_slice(sequencia);
+        self.tarefas.resize(n, 0);
+        self.pos = sequencia.len() as i32 - 1;
+    }
+
+    /// Retira a tarefa da posição `pos`, mantendo a ordem das demais.
+    pub fn remover(&mut self, pos: usize) -> usize {
+        let tarefa = self.tarefas.remove(pos);
+        self.tarefas.push(0);
+        self.pos -= 1;
+        tarefa
+    }
+
+    /// Instante de término da última tarefa, incluindo setups e esperas por liberação.
+    pub fn ms_maquina(&self) -> f64 {
+        self.instancia
+            .sequenciar(self.indice, self.ativas().iter().copied())
+            .last()
+            .map_or(0.0, |t| t.fim)
+    }
+
+    /// Instante em que a tarefa terminaria se fosse acrescentada ao fim da máquina.
+    pub fn fim_com(&self, tarefa: usize) -> f64 {
+        let tarefas = self.ativas().iter().copied().chain(std::iter::once(tarefa));
+        self.instancia
+            .sequenciar(self.indice, tarefas)
+            .last()
+            .map_or(0.0, |t| t.fim)
+    }
+
+    /// Início e fim de cada tarefa, processadas em sequência a partir do
+    /// instante 0; o setup e a espera pela liberação ficam antes do início.
+    pub fn cronograma(&self) -> Vec<TarefaAlocada> {
+        self.instancia
+            .sequenciar(self.indice, self.ativas().iter().copied())
+            .collect()
     }
 }
 
-pub fn ms_total(maquinas: &[Maquina]) -> u32 {
-    maquinas.iter().map(|m| m.ms_maquina()).max().unwrap_or(0)
+/// Sequência de tarefas de cada máquina.
+pub fn sequencias<'m>(maquinas: &'m [Maquina]) -> Vec<&'m [usize]> {
+    maquinas.iter().map(|m| m.ativas()).collect()
 }
 
-pub fn pos_ms_min(maquinas: &[Maquina]) -> usize {
+pub fn ms_total(maquinas: &[Maquina]) -> f64 {
+    maquinas.iter().map(|m| m.ms_maquina()).fold(0.0, f64::max)
+}
+
+/// Todas as tarefas da instância na máquina 0, na ordem original (ou numa
+/// ordem topológica, com precedências). Tarefas que não podem ir para a
+/// máquina 0 ficam na máquina elegível em que terminariam mais cedo. Na
+/// reprogramação, a solução inicial é a escala atual.
+pub fn solucao_inicial(instancia: &Instancia) -> Vec<Maquina<'_>> {
+    let mut maquinas: Vec<Maquina> = (0..instancia.n_maquinas())
+        .map(|i| Maquina::new(instancia, i))
+        .collect();
+    if let Some(reprogramacao) = &instancia.reprogramacao {
+        for (maquina, sequencia) in maquinas.iter_mut().zip(&reprogramacao.sequencias) {
+            maquina.definir(sequencia);
+        }
+        return maquinas;
+    }
+    let ordem = match &instancia.precedencias {
+        Some(grafo) => grafo.ordem(),
+        None => (0..instancia.n_tarefas()).collect(),
+    };
+    for i in ordem {
+        let destino = if maquinas[0].aceita(i) {
+            0
+        } else {
+            // `Instancia::restringir` garante ao menos uma máquina elegível
+            melhor_destino(&maquinas, i).map_or(0, |(d, _)| d)
+        };
+        maquinas[destino].adicionar(i);
+    }
+    maquinas
+}
+
+/// Máquina elegível (exceto a 0) em que a tarefa terminaria mais cedo, junto
+/// com esse instante. Empates ficam com o menor índice.
+pub fn melhor_destino(maquinas: &[Maquina], tarefa: usize) -> Option<(usize, f64)> {
     maquinas
         .iter()
         .enumerate()
-        .min_by_key(|(_, m)| m.ms_maquina())
-        .map(|(i, _)| i)
-        .unwrap_or(0)
+        .skip(1)
+        .filter(|(_, m)| m.aceita(tarefa))
+        .map(|(i, m)| (i, m.fim_com(tarefa)))
+        .fold(None, |melhor, (i, fim)| match melhor {
+            Some((_, f)) if f <= fim => melhor,
+            _ => Some((i, fim)),
+        })
 }
 
-pub fn search_max_value(maquina: &Maquina, filtrar_menor: u32) -> i32 {
+/// Cronograma de cada máquina. Com precedências as máquinas são programadas
+/// juntas; se as ordens forem inviáveis, cada máquina sai isolada e
+/// `Instancia::verificar` acusa a violação.
+pub fn extrair_alocacao(maquinas: &[Maquina]) -> Vec<Vec<TarefaAlocada>> {
+    maquinas
+        .first()
+        .filter(|m| m.instancia.precedencias.is_some())
+        .and_then(|m| m.instancia.programar(&sequencias(maquinas)))
+        .unwrap_or_else(|| maquinas.iter().map(|m| m.cronograma()).collect())
+}
+
+/// Posição da tarefa mais longa da máquina que satisfaz `permitida`
+/// (considerando apenas durações menores que `filtrar_menor`, se diferente
+/// de 0), ou -1 se não houver.
+pub fn search_max_value(
+    maquina: &Maquina,
+    filtrar_menor: f64,
+    permitida: impl Fn(usize) -> bool,
+) -> i32 {
     let mut pos = -1;
-    let mut valor = 0;
+    let mut valor = 0.0;
 
-    for i in 0..=(maquina.pos as usize) {
-        if maquina.tarefas[i] > valor && (filtrar_menor == 0 || maquina.tarefas[i] < filtrar_menor)
+    for (i, &tarefa) in maquina.ativas().iter().enumerate() {
+        let duracao = maquina.duracao(tarefa);
+        if duracao > valor && (filtrar_menor == 0.0 || duracao < filtrar_menor) && permitida(tarefa)
         {
-            valor = maquina.tarefas[i];
+            valor = duracao;
             pos = i as i32;
         }
     }
@@ -63,61 +199,473 @@ pub fn embaralhar_maquina(maquina: &mut Maquina, rng: &mut impl Rng) {
     }
 }
 
-pub fn melhor_melhora(tam_m: usize, tam_n: usize, tam_r: f64) -> Result {
-    let mut maquinas: Vec<Maquina> = (0..tam_m).map(|_| Maquina::new(tam_n)).collect();
-    let mut rng = rand::thread_rng();
+/// Embaralha a solução inicial antes da busca: sem precedências, a ordem da
+/// máquina 0; com elas, todas as máquinas passam a seguir uma mesma ordem
+/// topológica sorteada, o que mantém a solução viável. A escala atual da
+/// reprogramação é mantida como está.
+pub fn embaralhar_inicial(maquinas: &mut [Maquina], rng: &mut impl Rng) {
+    if maquinas
+        .first()
+        .is_some_and(|m| m.instancia.reprogramacao.is_some())
+    {
+        return;
+    }
+    let Some(grafo) = maquinas
+        .first()
+        .and_then(|m| m.instancia.precedencias.as_ref())
+    else {
+        if let Some(maquina) = maquinas.first_mut() {
+            embaralhar_maquina(maquina, rng);
+        }
+        return;
+    };
+    let posto = grafo.posto_aleatorio(rng);
+    for maquina in maquinas.iter_mut() {
+        let pos = (maquina.pos + 1) as usize;
+        maquina.tarefas[..pos].sort_by_key(|&t| posto[t]);
+    }
+}
+
+/// Faixa de posições em que a tarefa pode entrar na sequência sem ficar
+/// antes de um predecessor direto nem depois de um sucessor direto, ou `None`
+/// se não houver. Dependências indiretas são conferidas por `Instancia::programar`.
+pub fn janela_precedencias(
+    instancia: &Instancia,
+    sequencia: &[usize],
+    tarefa: usize,
+) -> Option<(usize, usize)> {
+    let predecessores = instancia.predecessores(tarefa);
+    let inicio = sequencia
+        .iter()
+        .rposition(|t| predecessores.contains(t))
+        .map_or(0, |k| k + 1);
+    let fim = sequencia
+        .iter()
+        .position(|&t| instancia.predecessores(t).contains(&tarefa))
+        .unwrap_or(sequencia.len());
+    (inicio <= fim).then_some((inicio, fim))
+}
 
-    for i in 0..tam_n {
-        let value = rng.gen_range(1..=100);
-        maquinas[0].tarefas[i] = value;
-        maquinas[0].pos += 1;
+/// Move a maior tarefa da máquina 0 que tenha outra máquina elegível para a
+/// máquina onde ela terminaria mais cedo, enquanto a origem estiver mais
+/// carregada que o destino e o destino não ultrapassar o makespan atual.
+/// Retorna se houve movimento.
+pub fn mover_maior_tarefa(maquinas: &mut [Maquina]) -> bool {
+    let ms = ms_total(maquinas);
+    let pos_max_value = search_max_value(&maquinas[0], 0.0, |tarefa| {
+        maquinas[1..].iter().any(|m| m.aceita(tarefa))
+    });
+    if pos_max_value == -1 {
+        return false;
     }
 
-    let ms_s = ms_total(&maquinas);
+    let tarefa = maquinas[0].tarefas[pos_max_value as usize];
+    let Some((destino, fim)) = melhor_destino(maquinas, tarefa) else {
+        return false;
+    };
 
-    // Embaralhar a máquina 0
-    embaralhar_maquina(&mut maquinas[0], &mut rng);
-    let tempo_s = Instant::now();
-    let mut moves = 0;
+    if maquinas[0].ms_maquina() <= maquinas[destino].ms_maquina() || fim > ms {
+        return false;
+    }
 
-    loop {
-        let ms = ms_total(&maquinas);
-        let pos_min = pos_ms_min(&maquinas);
+    maquinas[destino].adicionar(tarefa);
+    maquinas[0].remover(pos_max_value as usize);
+    true
+}
 
-        if pos_min == 0 {
-            break;
+/// Aplica a melhor reinserção de uma tarefa em outra posição da própria
+/// máquina, avaliando apenas a variação dos setups. Com datas de liberação a
+/// economia de setup pode virar espera, então o término da máquina é
+/// conferido e o movimento desfeito se não melhorar. Sem setup, mas com
+/// liberações, ordena a máquina pela liberação, o que minimiza seu término,
+/// e mantém a ordem anterior se o término não diminuir. Retorna se houve ganho.
+pub fn reordenar_maquina(maquina: &mut Maquina) -> bool {
+    if maquina.instancia.setup.is_none() {
+        if maquina.instancia.datas.is_none() {
+            return false;
+        }
+        let instancia = maquina.instancia;
+        let ms_antes = maquina.ms_maquina();
+        let original = maquina.ativas().to_vec();
+        let mut sequencia = original.clone();
+        sequencia.sort_by(|&a, &b| instancia.liberacao(a).total_cmp(&instancia.liberacao(b)));
+        maquina.definir(&sequencia);
+        if maquina.ms_maquina() < ms_antes - 1e-9 {
+            return true;
         }
+        maquina.definir(&original);
+        return false;
+    }
+    let ms_antes = maquina.ms_maquina();
 
-        let ms_n = maquinas[pos_min].ms_maquina();
-        let pos_max_value = search_max_value(&maquinas[0], 0);
+    let sequencia = maquina.ativas().to_vec();
+    let setup = |anterior: Option<usize>, tarefa: Option<usize>| {
+        tarefa.map_or(0.0, |t| maquina.instancia.setup(anterior, t))
+    };
+    let mut melhor = (-1e-9, 0, 0);
 
-        if pos_max_value == -1 || ms_n + maquinas[0].tarefas[pos_max_value as usize] > ms {
-            break;
+    for (i, &tarefa) in sequencia.iter().enumerate() {
+        let anterior = i.checked_sub(1).map(|k| sequencia[k]);
+        let proxima = sequencia.get(i + 1).copied();
+        let remocao =
+            setup(anterior, proxima) - setup(anterior, Some(tarefa)) - setup(Some(tarefa), proxima);
+
+        // Posições j na sequência sem a tarefa; j == i a devolveria ao lugar
+        let reduzida = |k: usize| sequencia[if k < i { k } else { k + 1 }];
+        for j in (0..sequencia.len()).filter(|&j| j != i) {
+            let antes = j.checked_sub(1).map(reduzida);
+            let depois = (j + 1 < sequencia.len()).then(|| reduzida(j));
+            let insercao =
+                setup(antes, Some(tarefa)) + setup(Some(tarefa), depois) - setup(antes, depois);
+            if remocao + insercao < melhor.0 {
+                melhor = (remocao + insercao, i, j);
+            }
         }
+    }
 
-        let tarefa = maquinas[0].tarefas[pos_max_value as usize];
-        maquinas[pos_min].pos += 1;
-        let pos = maquinas[pos_min].pos as usize;
-        maquinas[pos_min].tarefas[pos] = tarefa;
+    let (delta, de, para) = melhor;
+    if delta >= -1e-9 {
+        return false;
+    }
+    let tarefa = maquina.tarefas.remove(de);
+    maquina.tarefas.insert(para, tarefa);
+    if maquina.instancia.datas.is_some() && maquina.ms_maquina() >= ms_antes {
+        let tarefa = maquina.tarefas.remove(para);
+        maquina.tarefas.insert(de, tarefa);
+        return false;
+    }
+    true
+}
 
-        maquinas[0].tarefas.remove(pos_max_value as usize);
-        maquinas[0].tarefas.push(0);
-        maquinas[0].pos -= 1;
-        moves += 1;
+/// Reordena cada máquina uma vez. Retorna se alguma delas melhorou.
+pub fn reordenar_maquinas(maquinas: &mut [Maquina]) -> bool {
+    let mut melhorou = false;
+    for maquina in maquinas.iter_mut() {
+        melhorou |= reordenar_maquina(maquina);
     }
+    melhorou
+}
+
+/// Comparação lexicográfica das chaves de `Objetivo::chave`, com tolerância no valor.
+fn chave_menor(a: (f64, usize), b: (f64, usize)) -> bool {
+    a.0 < b.0 - 1e-9 || (a.0 <= b.0 + 1e-9 && a.1 < b.1)
+}
+
+/// Passada da busca local para objetivos diferentes do makespan (ou para
+/// qualquer objetivo na reprogramação, em que as migrações contam). Cada
+/// tarefa vai para o fim da máquina elegível que mais reduz o objetivo;
+/// depois, trocas de tarefas vizinhas que reduzem a contribuição de cada
+/// máquina. Retorna o número de movimentos aplicados.
+pub fn passada_objetivo(maquinas: &mut [Maquina], objetivo: Objetivo) -> usize {
+    let Some(instancia) = maquinas.first().map(|m| m.instancia) else {
+        return 0;
+    };
+    let mut custos: Vec<Custo> = maquinas.iter().map(|m| objetivo.custo(m)).collect();
+    let mut movimentos = 0;
+
+    for a in 0..maquinas.len() {
+        let mut i = 0;
+        while i < maquinas[a].ativas().len() {
+            let origem = &maquinas[a];
+            let tarefa = origem.tarefas[i];
+            let restantes = origem
+                .ativas()
+                .iter()
+                .enumerate()
+                .filter(|&(k, _)| k != i)
+                .map(|(_, &t)| t);
+            let sem = objetivo.custo_sequencia(origem, restantes);
+            let atual = objetivo.chave(instancia, custos.iter().copied());
+
+            // Destino com a menor chave (objetivo, máquinas críticas); empates
+            // ficam com o destino menos carregado depois do movimento
+            let mut melhor: Option<(usize, Custo, (f64, usize))> = None;
+            for (b, destino) in maquinas.iter().enumerate() {
+                if b == a || !destino.aceita(tarefa) {
+                    continue;
+                }
+                let tarefas = destino
+                    .ativas()
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(tarefa));
+                let com = objetivo.custo_sequencia(destino, tarefas);
+                let chave = objetivo.chave(
+                    instancia,
+                    custos.iter().enumerate().map(|(k, &c)| {
+                        if k == a {
+                            sem
+                        } else if k == b {
+                            com
+                        } else {
+                            c
+                        }
+                    }),
+                );
+                let melhora = match melhor {
+                    None => chave_menor(chave, atual),
+                    Some((_, com_melhor, chave_melhor)) => {
+                        chave_menor(chave, chave_melhor)
+                            || (!chave_menor(chave_melhor, chave)
+                                && objetivo.parcela(&com) < objetivo.parcela(&com_melhor))
+                    }
+                };
+                if melhora {
+                    melhor = Some((b, com, chave));
+                }
+            }
+
+            match melhor {
+                Some((b, com, _)) => {
+                    maquinas[a].remover(i);
+                    maquinas[b].adicionar(tarefa);
+                    custos[a] = sem;
+                    custos[b] = com;
+                    movimentos += 1;
+                }
+                None => i += 1,
+            }
+        }
+    }
+
+    for (a, maquina) in maquinas.iter_mut().enumerate() {
+        for i in 0..maquina.ativas().len().saturating_sub(1) {
+            maquina.tarefas.swap(i, i + 1);
+            // A troca só muda esta máquina: basta reduzir a parcela dela sem
+            // piorar o objetivo (no desequilíbrio, aliviar a máquina menos
+            // carregada piora)
+            let custo = objetivo.custo(maquina);
+            let chave = objetivo.chave(
+                instancia,
+                custos
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &c)| if k == a { custo } else { c }),
+            );
+            if objetivo.parcela(&custo) < objetivo.parcela(&custos[a]) - 1e-9
+                && !chave_menor(objetivo.chave(instancia, custos.iter().copied()), chave)
+            {
+                custos[a] = custo;
+                movimentos += 1;
+            } else {
+                maquina.tarefas.swap(i, i + 1);
+            }
+        }
+    }
+
+    movimentos
+}
+
+/// Chave do objetivo com todas as máquinas programadas juntas, ou `None` se
+/// as sequências violarem as precedências.
+fn chave_programada(
+    instancia: &Instancia,
+    objetivo: Objetivo,
+    sequencias: &[Vec<usize>],
+) -> Option<(f64, usize)> {
+    let referencias: Vec<&[usize]> = sequencias.iter().map(Vec::as_slice).collect();
+    let custos: Vec<Custo> = instancia
+        .programar(&referencias)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, cronograma)| objetivo.custo_cronograma(instancia, i, cronograma.into_iter()))
+        .collect();
+    Some(objetivo.chave(instancia, custos))
+}
+
+/// Passada da busca local com precedências. Como o início de uma tarefa
+/// depende de outras máquinas, cada movimento é avaliado programando a
+/// solução inteira. Cada tarefa vai para a primeira ou a última posição
+/// viável da máquina elegível que mais reduz o objetivo; depois, trocas de
+/// tarefas vizinhas sem precedência entre si. Movimentos que criariam
+/// bloqueio entre máquinas são descartados. Retorna o número de movimentos.
+pub fn passada_precedencias(maquinas: &mut [Maquina], objetivo: Objetivo) -> usize {
+    let Some(instancia) = maquinas.first().map(|m| m.instancia) else {
+        return 0;
+    };
+    let mut sequencias: Vec<Vec<usize>> = maquinas.iter().map(|m| m.ativas().to_vec()).collect();
+    let Some(mut atual) = chave_programada(instancia, objetivo, &sequencias) else {
+        return 0;
+    };
+    let mut movimentos = 0;
+
+    for a in 0..sequencias.len() {
+        let mut i = 0;
+        while i < sequencias[a].len() {
+            let tarefa = sequencias[a].remove(i);
+            let mut melhor: Option<(usize, usize, (f64, usize))> = None;
+            for b in (0..sequencias.len()).filter(|&b| b != a && instancia.elegivel(tarefa, b)) {
+                let Some((inicio, fim)) = janela_precedencias(instancia, &sequencias[b], tarefa)
+                else {
+                    continue;
+                };
+                let posicoes = if inicio == fim {
+                    vec![inicio]
+                } else {
+                    vec![inicio, fim]
+                };
+                for pos in posicoes {
+                    sequencias[b].insert(pos, tarefa);
+                    if let Some(chave) = chave_programada(instancia, objetivo, &sequencias) {
+                        if chave_menor(chave, melhor.map_or(atual, |(_, _, c)| c)) {
+                            melhor = Some((b, pos, chave));
+                        }
+                    }
+                    sequencias[b].remove(pos);
+                }
+            }
+
+            match melhor {
+                Some((b, pos, chave)) => {
+                    sequencias[b].insert(pos, tarefa);
+                    atual = chave;
+                    movimentos += 1;
+                }
+                None => {
+                    sequencias[a].insert(i, tarefa);
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    for a in 0..sequencias.len() {
+        for i in 0..sequencias[a].len().saturating_sub(1) {
+            let (primeira, segunda) = (sequencias[a][i], sequencias[a][i + 1]);
+            if instancia.predecessores(segunda).contains(&primeira) {
+                continue;
+            }
+            sequencias[a].swap(i, i + 1);
+            match chave_programada(instancia, objetivo, &sequencias) {
+                Some(chave) if chave_menor(chave, atual) => {
+                    atual = chave;
+                    movimentos += 1;
+                }
+                _ => sequencias[a].swap(i, i + 1),
+            }
+        }
+    }
+
+    for (maquina, sequencia) in maquinas.iter_mut().zip(&sequencias) {
+        maquina.definir(sequencia);
+    }
+    movimentos
+}
+
+/// Um passo da busca local: com precedências, uma passada que programa a
+/// solução inteira a cada movimento; no makespan sem reprogramação, um movimento da maior tarefa da
+/// máquina 0 ou, esgotados esses, uma reordenação por setup; nos demais
+/// objetivos, uma passada completa. Retorna quantos movimentos foram feitos.
+pub fn passo_busca_local(maquinas: &mut [Maquina], objetivo: Objetivo) -> usize {
+    if maquinas
+        .first()
+        .is_some_and(|m| m.instancia.precedencias.is_some())
+    {
+        return passada_precedencias(maquinas, objetivo);
+    }
+    match objetivo {
+        Objetivo::Makespan if maquinas[0].instancia.reprogramacao.is_none() => {
+            (mover_maior_tarefa(maquinas) || reordenar_maquinas(maquinas)) as usize
+        }
+        _ => passada_objetivo(maquinas, objetivo),
+    }
+}
+
+pub fn melhor_melhora(
+    instancia: &Instancia,
+    objetivo: Objetivo,
+    tam_r: Option<f64>,
+    semente: u64,
+    observador: &mut dyn FnMut(&PontoConvergencia) -> ControlFlow<()>,
+) -> Result {
+    let mut maquinas = solucao_inicial(instancia);
+    let mut rng = StdRng::seed_from_u64(semente);
+
+    let valor_inicial = objetivo.valor(&maquinas);
+
+    // Embaralhar a ordem inicial
+    embaralhar_inicial(&mut maquinas, &mut rng);
+    let tempo_s = Instant::now();
+    let mut moves = 0;
+    let mut convergencia = Vec::new();
+    let mut interrompida = false;
+
+    loop {
+        let valor = objetivo.avaliar(&maquinas);
+        let ponto = PontoConvergencia {
+            iteracao: moves,
+            tempo_ms: tempo_s.elapsed().as_secs_f64() * 1000.0,
+            valor_atual: valor,
+            valor_melhor: valor,
+            perturbacao: 0.0,
+        };
+        let fluxo = observador(&ponto);
+        convergencia.push(ponto);
+        if fluxo.is_break() {
+            interrompida = true;
+            break;
+        }
 
-    let ms_f = ms_total(&maquinas);
+        match passo_busca_local(&mut maquinas, objetivo) {
+            0 => break,
+            movimentos => moves += movimentos,
+        }
+    }
+
+    let valor_final = objetivo.valor(&maquinas);
+    let alocacao = extrair_alocacao(&maquinas);
     let tempo_exec = tempo_s.elapsed().as_secs_f64() * 1000.0;
 
     Result {
-        n_tarefas: tam_n,
-        n_maquinas: tam_m,
+        n_tarefas: instancia.n_tarefas(),
+        n_maquinas: instancia.n_maquinas(),
         replicacao: tam_r,
         tempo_exec,
         iteracoes: moves,
-        makespan_inicial: ms_s,
-        makespan_final: ms_f,
+        valor_inicial,
+        valor_final,
+        objetivo: objetivo.nome(),
         algoritmo: "busca-local-monotona-melhorada".to_string(),
         perturbacao: 0.0,
+        max_iteracoes: 0,
+        criterio_parada: if interrompida {
+            "interrompida"
+        } else {
+            "otimo-local"
+        }
+        .to_string(),
+        semente,
+        ambiente: instancia.ambiente.clone(),
+        limite_inferior: instancia.limite_inferior(),
+        referencia: instancia.referencia.clone(),
+        migracoes: instancia.migracoes(&alocacao),
+        alocacao,
+        convergencia,
+        frente: Vec::new(),
+        hipervolume: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Máquina única com as tarefas de tempo 5 liberadas nos instantes dados,
+    /// sequenciadas na ordem `sequencia`.
+    fn reordenar(liberacoes: &[u32], sequencia: &[usize]) -> (bool, Vec<usize>) {
+        let instancia =
+            Instancia::de_chegadas(vec![5; liberacoes.len()], liberacoes.to_vec(), vec![1.0]);
+        let mut maquina = Maquina::new(&instancia, 0);
+        maquina.definir(sequencia);
+        let melhorou = reordenar_maquina(&mut maquina);
+        (melhorou, maquina.ativas().to_vec())
+    }
+
+    #[test]
+    fn reordenar_pela_liberacao_so_quando_melhora() {
+        // A tarefa 1 espera até 9 e atrasa as outras duas
+        assert_eq!(reordenar(&[0, 9, 1], &[1, 0, 2]), (true, vec![0, 2, 1]));
+        // Sem espera nas duas ordens: mesmo término, ordem original preservada
+        assert_eq!(reordenar(&[0, 1, 3], &[0, 2, 1]), (false, vec![0, 2, 1]));
     }
 }
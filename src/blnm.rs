@@ -1,21 +1,118 @@
-use crate::blm::{embaralhar_maquina, ms_total, Maquina};
-use crate::utils::Result;
-use rand::Rng;
+use crate::blm::{
+    embaralhar_inicial, extrair_alocacao, janela_precedencias, passo_busca_local, sequencias,
+    solucao_inicial, Maquina,
+};
+use crate::instancia::Instancia;
+use crate::objetivo::Objetivo;
+use crate::utils::{PontoConvergencia, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::ops::ControlFlow;
 use std::time::Instant;
 
-fn clonar_solucao(maquinas: &[Maquina]) -> Vec<Maquina> {
-    maquinas
-        .iter()
-        .map(|m| Maquina {
-            tarefas: m.tarefas.clone(),
-            pos: m.pos,
-        })
-        .collect()
+/// Como a força da perturbação varia ao longo da busca local iterada.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Adaptacao {
+    /// A fração informada em todas as iterações.
+    Fixa,
+    /// Cresce a cada sequência de falhas consecutivas e volta à base na melhora.
+    Escalonada,
+    /// Ajustada pela taxa de melhoras nas últimas iterações (regra de 1/5).
+    Reativa,
+}
+
+impl Adaptacao {
+    pub const TODAS: [Adaptacao; 3] = [Adaptacao::Fixa, Adaptacao::Escalonada, Adaptacao::Reativa];
+
+    pub fn nome(&self) -> &'static str {
+        match self {
+            Adaptacao::Fixa => "fixa",
+            Adaptacao::Escalonada => "escalonada",
+            Adaptacao::Reativa => "reativa",
+        }
+    }
+
+    pub fn ler(texto: &str) -> Option<Self> {
+        Self::TODAS.into_iter().find(|a| a.nome() == texto)
+    }
+
+    /// Nome da heurística nos resultados, que separa as variantes na análise.
+    fn algoritmo(&self) -> &'static str {
+        match self {
+            Adaptacao::Fixa => "busca-local-iterada",
+            Adaptacao::Escalonada => "busca-local-iterada-escalonada",
+            Adaptacao::Reativa => "busca-local-iterada-reativa",
+        }
+    }
+}
+
+/// Falhas consecutivas que sobem a força escalonada em mais uma vez a base.
+const FALHAS_POR_NIVEL: u32 = 10;
+/// Iterações entre ajustes da força reativa e a taxa de melhoras visada.
+const JANELA_REATIVA: u32 = 10;
+const TAXA_ALVO: f64 = 0.2;
+const FATOR_REATIVO: f64 = 1.5;
+const FORCA_MINIMA: f64 = 0.01;
+
+/// Força corrente da perturbação e o histórico recente que a ajusta.
+struct Forca {
+    adaptacao: Adaptacao,
+    base: f64,
+    atual: f64,
+    falhas: u32,
+    iteracoes: u32,
+    melhoras: u32,
+}
+
+impl Forca {
+    fn new(adaptacao: Adaptacao, base: f64) -> Self {
+        Forca {
+            adaptacao,
+            base,
+            atual: base,
+            falhas: 0,
+            iteracoes: 0,
+            melhoras: 0,
+        }
+    }
+
+    fn registrar(&mut self, melhorou: bool) {
+        match self.adaptacao {
+            Adaptacao::Fixa => {}
+            Adaptacao::Escalonada => {
+                if melhorou {
+                    self.falhas = 0;
+                    self.atual = self.base;
+                } else {
+                    self.falhas += 1;
+                    if self.falhas.is_multiple_of(FALHAS_POR_NIVEL) {
+                        self.atual = (self.atual + self.base).min(1.0);
+                    }
+                }
+            }
+            Adaptacao::Reativa => {
+                self.iteracoes += 1;
+                self.melhoras += melhorou as u32;
+                if self.iteracoes == JANELA_REATIVA {
+                    // Muitas melhoras: dá para ir mais longe; poucas: voltar para perto
+                    let taxa = self.melhoras as f64 / JANELA_REATIVA as f64;
+                    if taxa > TAXA_ALVO {
+                        self.atual = (self.atual * FATOR_REATIVO).min(1.0);
+                    } else if taxa < TAXA_ALVO {
+                        self.atual = (self.atual / FATOR_REATIVO).max(FORCA_MINIMA);
+                    }
+                    self.iteracoes = 0;
+                    self.melhoras = 0;
+                }
+            }
+        }
+    }
 }
 
-fn perturbar(maquinas: &mut [Maquina], perturbacao: f64) {
-    let mut rng = rand::thread_rng();
+fn clonar_solucao<'a>(maquinas: &[Maquina<'a>]) -> Vec<Maquina<'a>> {
+    maquinas.to_vec()
+}
 
+fn perturbar(maquinas: &mut [Maquina], perturbacao: f64, rng: &mut impl Rng) {
     // Contar total de tarefas
     let total_tarefas: usize = maquinas
         .iter()
@@ -28,6 +125,19 @@ fn perturbar(maquinas: &mut [Maquina], perturbacao: f64) {
 
     let num_perturb = ((total_tarefas as f64) * perturbacao).max(1.0) as usize;
 
+    // Na reprogramação com orçamento, a perturbação não passa do limite de migrações
+    let instancia = maquinas[0].instancia;
+    let orcamento = instancia.reprogramacao.as_ref().and_then(|r| r.orcamento);
+    let mut migracoes: usize = maquinas
+        .iter()
+        .map(|m| {
+            m.ativas()
+                .iter()
+                .filter(|&&t| instancia.migrou(t, m.indice))
+                .count()
+        })
+        .sum();
+
     for _ in 0..num_perturb {
         // Encontrar máquina de origem aleatória que tenha tarefas
         let maquinas_com_tarefas: Vec<usize> = maquinas
@@ -47,118 +157,255 @@ fn perturbar(maquinas: &mut [Maquina], perturbacao: f64) {
         let pos_tarefa = rng.gen_range(0..=(maquinas[idx_origem].pos as usize));
         let tarefa = maquinas[idx_origem].tarefas[pos_tarefa];
 
-        // Selecionar máquina de destino diferente da origem
-        let mut idx_destino = rng.gen_range(0..maquinas.len());
-        while idx_destino == idx_origem && maquinas.len() > 1 {
-            idx_destino = rng.gen_range(0..maquinas.len());
+        // Selecionar máquina de destino elegível e diferente da origem
+        let depois = |i: usize| {
+            migracoes + instancia.migrou(tarefa, i) as usize
+                - instancia.migrou(tarefa, idx_origem) as usize
+        };
+        let destinos: Vec<usize> = (0..maquinas.len())
+            .filter(|&i| i != idx_origem && maquinas[i].aceita(tarefa))
+            .filter(|&i| orcamento.is_none_or(|k| depois(i) <= k))
+            .collect();
+        if destinos.is_empty() {
+            continue;
         }
-
-        // Remover tarefa da máquina de origem
-        maquinas[idx_origem].tarefas.remove(pos_tarefa);
-        maquinas[idx_origem].tarefas.push(0);
-        maquinas[idx_origem].pos -= 1;
-
-        // Adicionar tarefa à máquina de destino
-        maquinas[idx_destino].pos += 1;
-        let pos = maquinas[idx_destino].pos as usize;
-        maquinas[idx_destino].tarefas[pos] = tarefa;
-    }
-}
-
-fn aplicar_busca_local(maquinas: &mut [Maquina]) {
-    use crate::blm::{pos_ms_min, search_max_value};
-
-    loop {
-        let ms = ms_total(maquinas);
-        let pos_min = pos_ms_min(maquinas);
-
-        if pos_min == 0 {
-            break;
+        let idx_destino = destinos[rng.gen_range(0..destinos.len())];
+        // Só conta a migração quando o movimento fica
+        let migracoes_depois = depois(idx_destino);
+
+        maquinas[idx_origem].remover(pos_tarefa);
+        let instancia = maquinas[idx_destino].instancia;
+        if instancia.precedencias.is_none() {
+            maquinas[idx_destino].adicionar(tarefa);
+            migracoes = migracoes_depois;
+            continue;
         }
 
-        let ms_n = maquinas[pos_min].ms_maquina();
-        let pos_max_value = search_max_value(&maquinas[0], 0);
-
-        if pos_max_value == -1 || ms_n + maquinas[0].tarefas[pos_max_value as usize] > ms {
-            break;
+        // Com precedências, a tarefa entra numa posição sorteada entre seus
+        // predecessores e sucessores; se as máquinas se bloquearem, volta
+        let janela = janela_precedencias(instancia, maquinas[idx_destino].ativas(), tarefa);
+        let Some((inicio, fim)) = janela else {
+            maquinas[idx_origem].inserir(pos_tarefa, tarefa);
+            continue;
+        };
+        let pos_destino = rng.gen_range(inicio..=fim);
+        maquinas[idx_destino].inserir(pos_destino, tarefa);
+        if instancia.programar(&sequencias(maquinas)).is_none() {
+            maquinas[idx_destino].remover(pos_destino);
+            maquinas[idx_origem].inserir(pos_tarefa, tarefa);
+        } else {
+            migracoes = migracoes_depois;
         }
-
-        let tarefa = maquinas[0].tarefas[pos_max_value as usize];
-        maquinas[pos_min].pos += 1;
-        let pos = maquinas[pos_min].pos as usize;
-        maquinas[pos_min].tarefas[pos] = tarefa;
-
-        maquinas[0].tarefas.remove(pos_max_value as usize);
-        maquinas[0].tarefas.push(0);
-        maquinas[0].pos -= 1;
     }
 }
 
+fn aplicar_busca_local(maquinas: &mut [Maquina], objetivo: Objetivo) {
+    while passo_busca_local(maquinas, objetivo) > 0 {}
+}
+
+/// Busca local iterada a partir da melhor solução, com `perturbacao` como força
+/// inicial; `adaptacao` decide como ela muda a cada iteração.
+#[allow(clippy::too_many_arguments)]
 pub fn busca_local_iterada(
-    tam_m: usize,
-    tam_n: usize,
-    tam_r: f64,
+    instancia: &Instancia,
+    objetivo: Objetivo,
+    tam_r: Option<f64>,
     perturbacao: f64,
+    adaptacao: Adaptacao,
     max_iteracoes_sem_melhora: u32,
+    semente: u64,
+    observador: &mut dyn FnMut(&PontoConvergencia) -> ControlFlow<()>,
 ) -> Result {
-    let mut maquinas: Vec<Maquina> = (0..tam_m).map(|_| Maquina::new(tam_n)).collect();
-    let mut rng = rand::thread_rng();
-
-    // Gerar solução inicial aleatória
-    for i in 0..tam_n {
-        let value = rng.gen_range(1..=100);
-        maquinas[0].tarefas[i] = value;
-        maquinas[0].pos += 1;
-    }
+    let mut maquinas = solucao_inicial(instancia);
+    let mut rng = StdRng::seed_from_u64(semente);
 
     // Randomizar ordem das tarefas na máquina 0 a cada iteração
-    embaralhar_maquina(&mut maquinas[0], &mut rng);
+    embaralhar_inicial(&mut maquinas, &mut rng);
 
-    let ms_s = ms_total(&maquinas);
+    let valor_inicial = objetivo.valor(&maquinas);
     let tempo_s = Instant::now();
 
     // Aplicar busca local na solução inicial
     let mut melhor_solucao = clonar_solucao(&maquinas);
-    aplicar_busca_local(&mut melhor_solucao);
-    let mut melhor_makespan = ms_total(&melhor_solucao);
+    aplicar_busca_local(&mut melhor_solucao, objetivo);
+    let mut melhor_valor = objetivo.avaliar(&melhor_solucao);
 
     let mut iteracoes_sem_melhora = 0;
     let mut iteracoes_totais = 0;
 
-    while iteracoes_sem_melhora < max_iteracoes_sem_melhora {
+    let mut forca = Forca::new(adaptacao, perturbacao);
+
+    let mut convergencia = Vec::new();
+    let mut registrar = |iteracao: usize, atual: f64, melhor: f64, perturbacao: f64| {
+        let ponto = PontoConvergencia {
+            iteracao,
+            tempo_ms: tempo_s.elapsed().as_secs_f64() * 1000.0,
+            valor_atual: atual,
+            valor_melhor: melhor,
+            perturbacao,
+        };
+        let fluxo = observador(&ponto);
+        convergencia.push(ponto);
+        fluxo
+    };
+    let mut interrompida = registrar(0, melhor_valor, melhor_valor, 0.0).is_break();
+
+    while !interrompida && iteracoes_sem_melhora < max_iteracoes_sem_melhora {
         // Perturbar a melhor solução
         let mut solucao_perturbada = clonar_solucao(&melhor_solucao);
-        perturbar(&mut solucao_perturbada, perturbacao);
+        let forca_usada = forca.atual;
+        perturbar(&mut solucao_perturbada, forca_usada, &mut rng);
 
         // Aplicar busca local
-        aplicar_busca_local(&mut solucao_perturbada);
+        aplicar_busca_local(&mut solucao_perturbada, objetivo);
 
         // Avaliar nova solução
-        let makespan_atual = ms_total(&solucao_perturbada);
+        let valor_atual = objetivo.avaliar(&solucao_perturbada);
 
         // Aceitar se melhor
-        if makespan_atual < melhor_makespan {
+        let melhorou = valor_atual < melhor_valor;
+        if melhorou {
             melhor_solucao = solucao_perturbada;
-            melhor_makespan = makespan_atual;
+            melhor_valor = valor_atual;
             iteracoes_sem_melhora = 0;
         } else {
             iteracoes_sem_melhora += 1;
         }
+        forca.registrar(melhorou);
 
         iteracoes_totais += 1;
+        interrompida =
+            registrar(iteracoes_totais, valor_atual, melhor_valor, forca_usada).is_break();
     }
 
     let tempo_exec = tempo_s.elapsed().as_secs_f64() * 1000.0;
+    let alocacao = extrair_alocacao(&melhor_solucao);
 
     Result {
-        n_tarefas: tam_n,
-        n_maquinas: tam_m,
+        n_tarefas: instancia.n_tarefas(),
+        n_maquinas: instancia.n_maquinas(),
         replicacao: tam_r,
         tempo_exec,
         iteracoes: iteracoes_totais,
-        makespan_inicial: ms_s,
-        makespan_final: melhor_makespan,
-        algoritmo: "busca-local-iterada".to_string(),
+        valor_inicial,
+        valor_final: objetivo.valor(&melhor_solucao),
+        objetivo: objetivo.nome(),
+        algoritmo: adaptacao.algoritmo().to_string(),
         perturbacao,
+        max_iteracoes: max_iteracoes_sem_melhora,
+        criterio_parada: if interrompida {
+            "interrompida"
+        } else {
+            "max-iter-sem-melhora"
+        }
+        .to_string(),
+        semente,
+        ambiente: instancia.ambiente.clone(),
+        limite_inferior: instancia.limite_inferior(),
+        referencia: instancia.referencia.clone(),
+        migracoes: instancia.migracoes(&alocacao),
+        alocacao,
+        convergencia,
+        frente: Vec::new(),
+        hipervolume: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instancia::{Ambiente, Distribuicao};
+    use crate::objetivo::OBJETIVOS;
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    /// Registra `quantas` iterações iguais e devolve a força resultante.
+    fn repetir(forca: &mut Forca, melhorou: bool, quantas: u32) -> f64 {
+        for _ in 0..quantas {
+            forca.registrar(melhorou);
+        }
+        forca.atual
+    }
+
+    #[test]
+    fn fixa_nao_muda() {
+        let mut forca = Forca::new(Adaptacao::Fixa, 0.3);
+        assert_eq!(repetir(&mut forca, false, 100), 0.3);
+        assert_eq!(repetir(&mut forca, true, 100), 0.3);
+    }
+
+    #[test]
+    fn escalonada_sobe_por_nivel_ate_1_e_volta_na_melhora() {
+        let mut forca = Forca::new(Adaptacao::Escalonada, 0.3);
+        assert_eq!(repetir(&mut forca, false, FALHAS_POR_NIVEL - 1), 0.3);
+        assert!(perto(repetir(&mut forca, false, 1), 0.6));
+        assert!(perto(repetir(&mut forca, false, FALHAS_POR_NIVEL), 0.9));
+        assert_eq!(repetir(&mut forca, false, FALHAS_POR_NIVEL), 1.0);
+        assert_eq!(repetir(&mut forca, false, 5 * FALHAS_POR_NIVEL), 1.0);
+
+        assert_eq!(repetir(&mut forca, true, 1), 0.3);
+        // A contagem de falhas recomeça depois da melhora
+        assert_eq!(repetir(&mut forca, false, FALHAS_POR_NIVEL - 1), 0.3);
+        assert!(perto(repetir(&mut forca, false, 1), 0.6));
+    }
+
+    #[test]
+    fn reativa_segue_a_regra_de_um_quinto_por_janela() {
+        let janela = |forca: &mut Forca, melhoras: u32| {
+            repetir(forca, true, melhoras);
+            repetir(forca, false, JANELA_REATIVA - melhoras)
+        };
+        let mut forca = Forca::new(Adaptacao::Reativa, 0.3);
+        // Nada muda no meio da janela
+        assert_eq!(repetir(&mut forca, true, JANELA_REATIVA - 1), 0.3);
+        assert!(perto(repetir(&mut forca, true, 1), 0.3 * FATOR_REATIVO));
+
+        let mut forca = Forca::new(Adaptacao::Reativa, 0.3);
+        let no_alvo = (TAXA_ALVO * JANELA_REATIVA as f64) as u32;
+        assert_eq!(janela(&mut forca, no_alvo), 0.3);
+        assert!(perto(janela(&mut forca, no_alvo + 1), 0.3 * FATOR_REATIVO));
+        assert!(perto(janela(&mut forca, no_alvo - 1), 0.3));
+        assert!(perto(
+            janela(&mut forca, JANELA_REATIVA),
+            0.3 * FATOR_REATIVO
+        ));
+
+        for _ in 0..10 {
+            janela(&mut forca, JANELA_REATIVA);
+        }
+        assert_eq!(forca.atual, 1.0);
+        for _ in 0..50 {
+            janela(&mut forca, 0);
+        }
+        assert_eq!(forca.atual, FORCA_MINIMA);
+    }
+
+    #[test]
+    fn convergencia_registra_a_forca_usada() {
+        let instancia = Instancia::gerar(30, 4, &Ambiente::padroes()[0], &Distribuicao::PADRAO, 7);
+        for adaptacao in [Adaptacao::Escalonada, Adaptacao::Reativa] {
+            let resultado = busca_local_iterada(
+                &instancia,
+                OBJETIVOS[0],
+                None,
+                0.1,
+                adaptacao,
+                40,
+                11,
+                &mut |_| ControlFlow::Continue(()),
+            );
+            let pontos = &resultado.convergencia;
+            assert_eq!(pontos[0].perturbacao, 0.0);
+
+            // Refaz a sequência de forças a partir das melhoras registradas
+            let mut forca = Forca::new(adaptacao, 0.1);
+            for par in pontos.windows(2) {
+                assert_eq!(par[1].perturbacao, forca.atual, "{adaptacao:?}");
+                forca.registrar(par[1].valor_melhor < par[0].valor_melhor);
+            }
+            assert!(pontos.iter().any(|p| p.perturbacao != 0.1), "{adaptacao:?}");
+        }
     }
 }
@@ -0,0 +1,193 @@
+use crate::benchmark;
+use crate::config;
+use crate::instancia::{
+    ler_velocidades, Ambiente, Distribuicao, Elegibilidade, Instancia, PerfilVelocidade,
+    Referencia, Reprogramacao, Setup,
+};
+use crate::objetivo::Objetivo;
+use crate::precedencia::ler_arcos;
+use crate::ui::{App, CampoTexto};
+use crate::utils::{ler_lista, FormatoCsv};
+use std::io;
+
+const USO: &str = "uso: task-scheduling-heuristics [--config <arquivo>] [--diretorio <dir>] [--arquivo <nome.csv>] \
+[--m <lista>] [--r <lista>] [--n <lista>] [--execucoes <k>] [--adaptacao fixa|escalonada|reativa] \
+[--historico <csv|diretório>]... [--jsonl] [--sqlite] [--legado] [--velocidades <s1,s2,...>] [--matriz <arquivo>] \
+[--benchmark <arquivo|diretório>]... [--catalogo <melhores.csv>] \
+[--elegibilidade <arquivo>] [--setup <arquivo>] [--precedencias <arquivo>] \
+[--objetivo <nome|combinacao:a,b,c>] [--distribuicao <tipo:parâmetros>] \
+[--escala <arquivo> [--max-migracoes <k>] [--penalidade-migracao <λ>]]
+       task-scheduling-heuristics analyze <resultados.csv> [--saida <analise.csv>]
+       task-scheduling-heuristics online [<chegadas.txt>|-] [--m <m>] [--politica <nome>]... (--help)
+       task-scheduling-heuristics tune [--algoritmo ils|pareto] [--orcamento <execuções>] ... (--help)";
+
+fn argumento_invalido(mensagem: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("{mensagem}\n{USO}"))
+}
+
+/// Aplica as opções de linha de comando do modo interativo sobre a configuração inicial,
+/// depois de carregar o arquivo de configuração.
+pub fn aplicar_opcoes(app: &mut App, args: &[String]) -> io::Result<()> {
+    // O arquivo é lido antes das demais opções, que prevalecem sobre ele
+    if let Some(i) = args.iter().position(|a| a == "--config") {
+        app.arquivo_config = args
+            .get(i + 1)
+            .cloned()
+            .ok_or_else(|| argumento_invalido("--config exige um valor".to_string()))?;
+    }
+    config::carregar(app)?;
+
+    let mut catalogo = None;
+    let mut orcamento = None;
+    let mut penalidade = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut valor = |opcao: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| argumento_invalido(format!("{opcao} exige um valor")))
+        };
+        match arg.as_str() {
+            "--config" => {
+                valor(arg)?;
+            }
+            "--diretorio" | "-d" => app.saida.diretorio = valor(arg)?,
+            "--arquivo" | "-f" => app.saida.arquivo = valor(arg)?,
+            "--jsonl" => app.saida.jsonl = true,
+            "--sqlite" => app.saida.sqlite = true,
+            "--legado" => app.saida.formato_csv = FormatoCsv::Legado,
+            "--m" | "--r" | "--n" => {
+                let texto = valor(arg)?;
+                let valores = ler_lista(&texto)
+                    .filter(|v| {
+                        v.iter()
+                            .all(|&x| x > 0.0 && (arg == "--r" || x.fract() == 0.0))
+                    })
+                    .ok_or_else(|| {
+                        argumento_invalido(format!(
+                            "{arg} espera números positivos (inteiros para m e n), em lista \
+                             ou faixa a..b:passo: {texto}"
+                        ))
+                    })?;
+                match arg.as_str() {
+                    "--m" => app.m_values = valores.into_iter().map(|m| m as usize).collect(),
+                    "--r" => app.r_values = valores,
+                    _ => app.n_values = valores.into_iter().map(|n| n as usize).collect(),
+                }
+            }
+            "--historico" => app.historico_fontes.push(valor(arg)?),
+            "--execucoes" => {
+                let texto = valor(arg)?;
+                app.definir_campo(CampoTexto::Execucoes, &texto)
+                    .map_err(|e| argumento_invalido(format!("--execucoes: {e}: {texto}")))?;
+            }
+            "--adaptacao" => {
+                let texto = valor(arg)?;
+                app.definir_campo(CampoTexto::Adaptacao, &texto)
+                    .map_err(|e| argumento_invalido(format!("--adaptacao: {e}: {texto}")))?;
+            }
+            "--velocidades" => {
+                let texto = valor(arg)?;
+                let velocidades = ler_velocidades(&texto)
+                    .ok_or_else(|| argumento_invalido(format!("velocidades inválidas: {texto}")))?;
+                app.ambientes
+                    .push(Ambiente::Uniformes(PerfilVelocidade::Lista(velocidades)));
+                app.selected_ambiente = app.ambientes.len() - 1;
+            }
+            "--matriz" => {
+                let caminho = valor(arg)?;
+                let mut instancia = Instancia::ler_matriz(&caminho)?;
+                instancia.referencia = Some(Referencia {
+                    nome: benchmark::nome_instancia(&caminho),
+                    melhor_conhecido: None,
+                    otimo: false,
+                });
+                app.instancias_arquivo.push((caminho, instancia));
+                app.selected_instancia_arquivo = app.instancias_arquivo.len() - 1;
+            }
+            "--benchmark" => {
+                app.instancias_arquivo
+                    .extend(benchmark::carregar(&valor(arg)?)?);
+            }
+            "--catalogo" => catalogo = Some(benchmark::ler_catalogo(&valor(arg)?)?),
+            "--elegibilidade" => {
+                app.elegibilidades.push(Elegibilidade::ler(&valor(arg)?)?);
+                app.selected_elegibilidade = app.elegibilidades.len() - 1;
+            }
+            "--setup" => {
+                app.setups.push(Setup::ler(&valor(arg)?)?);
+                app.selected_setup = app.setups.len() - 1;
+            }
+            "--precedencias" => {
+                let caminho = valor(arg)?;
+                let arcos = ler_arcos(&caminho)?;
+                app.precedencias = Some((caminho, arcos));
+            }
+            "--objetivo" => {
+                let texto = valor(arg)?;
+                let objetivo = Objetivo::ler(&texto)
+                    .ok_or_else(|| argumento_invalido(format!("objetivo inválido: {texto}")))?;
+                match app.objetivos.iter().position(|o| *o == objetivo) {
+                    Some(i) => app.selected_objetivo = i,
+                    None => {
+                        app.objetivos.push(objetivo);
+                        app.selected_objetivo = app.objetivos.len() - 1;
+                    }
+                }
+            }
+            "--distribuicao" => {
+                let texto = valor(arg)?;
+                let distribuicao = Distribuicao::ler(&texto)
+                    .ok_or_else(|| argumento_invalido(format!("distribuição inválida: {texto}")))?;
+                match app.distribuicoes.iter().position(|d| *d == distribuicao) {
+                    Some(i) => app.selected_distribuicao = i,
+                    None => {
+                        app.distribuicoes.push(distribuicao);
+                        app.selected_distribuicao = app.distribuicoes.len() - 1;
+                    }
+                }
+            }
+            "--escala" => app.reprogramacao = Some(Reprogramacao::ler(&valor(arg)?)?),
+            "--max-migracoes" => {
+                let texto = valor(arg)?;
+                let k = texto.parse().map_err(|_| {
+                    argumento_invalido(format!("máximo de migrações inválido: {texto}"))
+                })?;
+                orcamento = Some(k);
+            }
+            "--penalidade-migracao" => {
+                let texto = valor(arg)?;
+                let lambda = texto
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|l| l.is_finite() && *l >= 0.0)
+                    .ok_or_else(|| {
+                        argumento_invalido(format!("penalidade de migração inválida: {texto}"))
+                    })?;
+                penalidade = Some(lambda);
+            }
+            "--help" | "-h" => return Err(argumento_invalido(String::new())),
+            outro => return Err(argumento_invalido(format!("opção desconhecida: {outro}"))),
+        }
+    }
+
+    if let Some(catalogo) = catalogo {
+        if app.instancias_arquivo.is_empty() {
+            return Err(argumento_invalido(
+                "--catalogo exige --benchmark ou --matriz".to_string(),
+            ));
+        }
+        benchmark::aplicar_catalogo(&mut app.instancias_arquivo, &catalogo);
+    }
+
+    if orcamento.is_some() || penalidade.is_some() {
+        let reprogramacao = app.reprogramacao.as_mut().ok_or_else(|| {
+            argumento_invalido(
+                "--max-migracoes e --penalidade-migracao exigem --escala".to_string(),
+            )
+        })?;
+        reprogramacao.orcamento = orcamento.or(reprogramacao.orcamento);
+        reprogramacao.penalidade = penalidade.unwrap_or(reprogramacao.penalidade);
+    }
+    Ok(())
+}
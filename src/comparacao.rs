@@ -0,0 +1,220 @@
+use crate::blm::melhor_melhora;
+use crate::blnm::{busca_local_iterada, Adaptacao};
+use crate::estatistica::{resumir, wilcoxon_pareado};
+use crate::instancia::Instancia;
+use crate::objetivo::Objetivo;
+use crate::pareto::busca_pareto;
+use crate::utils::{PontoConvergencia, Result};
+use std::ops::ControlFlow;
+
+/// Heurística do menu e os parâmetros com que ela é executada.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Configuracao {
+    /// Índice do algoritmo no menu: 0 BLM, 1 ILS, 2 Pareto.
+    pub algoritmo: usize,
+    pub perturbacao: f64,
+    /// Variação da perturbação ao longo da ILS; ignorada pelos demais algoritmos.
+    pub adaptacao: Adaptacao,
+    pub max_iter: u32,
+}
+
+impl Configuracao {
+    pub fn nome(&self) -> String {
+        match self.algoritmo {
+            0 => "BLM".to_string(),
+            1 => match self.adaptacao {
+                Adaptacao::Fixa => format!("ILS(p={}, {})", self.perturbacao, self.max_iter),
+                adaptacao => format!(
+                    "ILS(p={} {}, {})",
+                    self.perturbacao,
+                    adaptacao.nome(),
+                    self.max_iter
+                ),
+            },
+            _ => format!("PLS({})", self.max_iter),
+        }
+    }
+
+    /// Executa a heurística com estes parâmetros; a busca de Pareto ignora o objetivo.
+    pub fn executar(
+        &self,
+        instancia: &Instancia,
+        objetivo: Objetivo,
+        tam_r: Option<f64>,
+        semente: u64,
+        observador: &mut dyn FnMut(&PontoConvergencia) -> ControlFlow<()>,
+    ) -> Result {
+        match self.algoritmo {
+            0 => melhor_melhora(instancia, objetivo, tam_r, semente, observador),
+            1 => busca_local_iterada(
+                instancia,
+                objetivo,
+                tam_r,
+                self.perturbacao,
+                self.adaptacao,
+                self.max_iter,
+                semente,
+                observador,
+            ),
+            _ => busca_pareto(instancia, tam_r, self.max_iter, semente, observador),
+        }
+    }
+}
+
+/// Desempenho de uma configuração contra a primeira (a referência) nas mesmas replicações.
+pub struct Placar {
+    pub vitorias: usize,
+    pub empates: usize,
+    pub derrotas: usize,
+    pub media: f64,
+    /// Média do desvio (%) ao melhor valor da replicação entre as configurações.
+    pub gap_medio: f64,
+    /// Diferença, em pontos percentuais, entre o gap médio e o da referência.
+    pub diferenca_gap: f64,
+    /// Wilcoxon pareado contra a referência; `None` para a própria referência.
+    pub p_valor: Option<f64>,
+    pub tempo_medio: f64,
+}
+
+/// Configurações executadas sobre as mesmas instâncias: na replicação i, todas
+/// usam a mesma semente para gerar a instância e para o próprio algoritmo.
+pub struct Comparacao {
+    pub configuracoes: Vec<Configuracao>,
+    pub sementes: Vec<u64>,
+    /// Valor final por replicação, na ordem das configurações.
+    pub valores: Vec<Vec<f64>>,
+    pub tempos: Vec<Vec<f64>>,
+}
+
+impl Comparacao {
+    pub fn new(configuracoes: Vec<Configuracao>) -> Self {
+        Comparacao {
+            configuracoes,
+            sementes: Vec::new(),
+            valores: Vec::new(),
+            tempos: Vec::new(),
+        }
+    }
+
+    /// Desvio (%) de cada configuração ao melhor valor obtido na replicação.
+    pub fn gaps(&self, replicacao: usize) -> Vec<f64> {
+        let valores = &self.valores[replicacao];
+        let melhor = valores.iter().copied().fold(f64::INFINITY, f64::min);
+        valores
+            .iter()
+            .map(|v| {
+                if melhor == 0.0 {
+                    if *v == 0.0 {
+                        0.0
+                    } else {
+                        100.0
+                    }
+                } else {
+                    (v - melhor) / melhor.abs() * 100.0
+                }
+            })
+            .collect()
+    }
+
+    pub fn placar(&self, k: usize) -> Placar {
+        const TOLERANCIA: f64 = 1e-9;
+        let coluna = |j: usize| -> Vec<f64> { self.valores.iter().map(|v| v[j]).collect() };
+        let (valores, referencia) = (coluna(k), coluna(0));
+        let gaps =
+            |j: usize| -> Vec<f64> { (0..self.valores.len()).map(|i| self.gaps(i)[j]).collect() };
+        let gap_medio = resumir(&gaps(k)).media;
+        let tempos: Vec<f64> = self.tempos.iter().map(|t| t[k]).collect();
+
+        let diferencas = valores.iter().zip(&referencia).map(|(v, r)| v - r);
+        Placar {
+            vitorias: diferencas.clone().filter(|d| *d < -TOLERANCIA).count(),
+            empates: diferencas.clone().filter(|d| d.abs() <= TOLERANCIA).count(),
+            derrotas: diferencas.filter(|d| *d > TOLERANCIA).count(),
+            media: resumir(&valores).media,
+            gap_medio,
+            diferenca_gap: gap_medio - resumir(&gaps(0)).media,
+            p_valor: (k > 0 && !valores.is_empty())
+                .then(|| wilcoxon_pareado(&valores, &referencia).0.p_valor),
+            tempo_medio: resumir(&tempos).media,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    fn comparacao() -> Comparacao {
+        let mut comparacao = Comparacao::new(Vec::new());
+        comparacao.valores = vec![
+            // Diferença abaixo da tolerância conta como empate
+            vec![10.0, 10.0 + 1e-12, 12.0],
+            vec![10.0, 8.0, 10.0],
+            // Melhor valor zero: gap 0 para quem o atinge, 100% para os demais
+            vec![0.0, 0.0, 5.0],
+            vec![20.0, 25.0, 15.0],
+        ];
+        comparacao.tempos = vec![vec![1.0, 2.0, 3.0]; 4];
+        comparacao
+    }
+
+    #[test]
+    fn gaps_por_replicacao() {
+        let comparacao = comparacao();
+        let casos: [(usize, [f64; 3]); 4] = [
+            (0, [0.0, 0.0, 20.0]),
+            (1, [25.0, 0.0, 25.0]),
+            (2, [0.0, 0.0, 100.0]),
+            (3, [100.0 / 3.0, 200.0 / 3.0, 0.0]),
+        ];
+        for (replicacao, esperados) in casos {
+            let gaps = comparacao.gaps(replicacao);
+            assert!(
+                gaps.iter().zip(esperados).all(|(g, e)| perto(*g, e)),
+                "replicação {replicacao}: {gaps:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn placar_contra_a_referencia() {
+        let comparacao = comparacao();
+        let gap_referencia = (25.0 + 100.0 / 3.0) / 4.0;
+        // (configuração, vitórias, empates, derrotas, gap médio, diferença de gap)
+        let casos = [
+            (0, 0, 4, 0, gap_referencia, 0.0),
+            (
+                1,
+                1,
+                2,
+                1,
+                200.0 / 3.0 / 4.0,
+                200.0 / 3.0 / 4.0 - gap_referencia,
+            ),
+            (2, 1, 1, 2, 145.0 / 4.0, 145.0 / 4.0 - gap_referencia),
+        ];
+        for (k, vitorias, empates, derrotas, gap_medio, diferenca_gap) in casos {
+            let placar = comparacao.placar(k);
+            assert_eq!(
+                (placar.vitorias, placar.empates, placar.derrotas),
+                (vitorias, empates, derrotas),
+                "configuração {k}"
+            );
+            assert!(perto(placar.gap_medio, gap_medio), "configuração {k}");
+            assert!(
+                perto(placar.diferenca_gap, diferenca_gap),
+                "configuração {k}"
+            );
+            assert_eq!(placar.tempo_medio, (k + 1) as f64);
+
+            let coluna =
+                |j: usize| -> Vec<f64> { comparacao.valores.iter().map(|v| v[j]).collect() };
+            let esperado = (k > 0).then(|| wilcoxon_pareado(&coluna(k), &coluna(0)).0.p_valor);
+            assert_eq!(placar.p_valor, esperado, "configuração {k}");
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::ui::{App, CampoTexto};
+use std::fs;
+use std::io;
+
+/// Arquivo de configuração usado quando --config não é informado, no diretório de trabalho.
+pub const ARQUIVO_PADRAO: &str = "task-scheduling-heuristics.conf";
+
+/// Aplica as linhas `chave = valor` de `app.arquivo_config` sobre a configuração
+/// atual. Sem o arquivo, os valores padrão ficam inalterados.
+pub fn carregar(app: &mut App) -> io::Result<()> {
+    let caminho = app.arquivo_config.clone();
+    let conteudo = match fs::read_to_string(&caminho) {
+        Ok(conteudo) => conteudo,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(io::Error::new(e.kind(), format!("{caminho}: {e}"))),
+    };
+
+    for (i, linha) in conteudo.lines().enumerate() {
+        let linha = linha.trim();
+        if linha.is_empty() || linha.starts_with('#') {
+            continue;
+        }
+        let invalido = |msg: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{caminho}:{}: {msg}", i + 1),
+            )
+        };
+        let (chave, valor) = linha
+            .split_once('=')
+            .ok_or_else(|| invalido("esperado chave = valor".to_string()))?;
+        let chave = chave.trim();
+        let campo = CampoTexto::TODOS
+            .into_iter()
+            .find(|c| c.chave() == chave)
+            .ok_or_else(|| invalido(format!("chave desconhecida: {chave}")))?;
+        app.definir_campo(campo, valor)
+            .map_err(|e| invalido(format!("{chave}: {e}")))?;
+    }
+    lembrar(app, &CampoTexto::TODOS);
+    Ok(())
+}
+
+/// Passa o valor atual de `campos` a ser o valor a gravar no arquivo.
+fn lembrar(app: &mut App, campos: &[CampoTexto]) {
+    for campo in campos {
+        if let Some(i) = CampoTexto::TODOS
+            .iter()
+            .position(|c| c.chave() == campo.chave())
+        {
+            app.valores_config[i] = app.texto_campo(*campo);
+        }
+    }
+}
+
+/// Grava em `app.arquivo_config` o valor atual de `campos` (os editados no
+/// menu); os demais campos saem como foram lidos do arquivo, de modo que
+/// opções de linha de comando valem só para esta sessão.
+pub fn salvar(app: &mut App, campos: &[CampoTexto]) -> io::Result<()> {
+    lembrar(app, campos);
+    let mut conteudo =
+        String::from("# Parâmetros do menu, regravados a cada edição e lidos ao iniciar\n");
+    for (campo, valor) in CampoTexto::TODOS.iter().zip(&app.valores_config) {
+        let linha = format!("{} = {valor}", campo.chave());
+        conteudo.push_str(linha.trim_end());
+        conteudo.push('\n');
+    }
+    fs::write(&app.arquivo_config, conteudo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ArquivoTemporario;
+
+    #[test]
+    fn salvar_nao_grava_opcoes_da_linha_de_comando() {
+        let arquivo = ArquivoTemporario::com("opcoes.conf", "execucoes = 5\nmaquinas = 4\n");
+        let mut app = App::new();
+        app.arquivo_config = arquivo.texto();
+        carregar(&mut app).unwrap();
+        assert_eq!(app.execucoes, 5);
+
+        // --execucoes 50 na linha de comando, depois uma edição no menu
+        app.definir_campo(CampoTexto::Execucoes, "50").unwrap();
+        app.definir_campo(CampoTexto::Maquinas, "8,16").unwrap();
+        salvar(&mut app, &[CampoTexto::Maquinas]).unwrap();
+        let conteudo = fs::read_to_string(arquivo.caminho()).unwrap();
+        assert!(conteudo.contains("execucoes = 5\n"));
+        assert!(conteudo.contains("maquinas = 8,16\n"));
+        assert_eq!(app.execucoes, 50);
+    }
+
+    #[test]
+    fn carregar_recusa_chave_desconhecida() {
+        let arquivo = ArquivoTemporario::com("invalido.conf", "# comentário\nvelocidade = 3\n");
+        let mut app = App::new();
+        app.arquivo_config = arquivo.texto();
+        let erro = carregar(&mut app).unwrap_err();
+        assert_eq!(erro.kind(), io::ErrorKind::InvalidData);
+        assert!(erro
+            .to_string()
+            .ends_with(":2: chave desconhecida: velocidade"));
+    }
+}
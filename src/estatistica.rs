@@ -0,0 +1,487 @@
+use crate::utils::Result;
+
+pub struct Resumo {
+    pub min: f64,
+    pub media: f64,
+    pub mediana: f64,
+    pub max: f64,
+    pub desvio: f64,
+}
+
+pub fn resumir(valores: &[f64]) -> Resumo {
+    if valores.is_empty() {
+        return Resumo {
+            min: 0.0,
+            media: 0.0,
+            mediana: 0.0,
+            max: 0.0,
+            desvio: 0.0,
+        };
+    }
+
+    let mut ordenados = valores.to_vec();
+    ordenados.sort_by(|a, b| a.total_cmp(b));
+
+    let n = ordenados.len();
+    let media = ordenados.iter().sum::<f64>() / n as f64;
+    let mediana = if n.is_multiple_of(2) {
+        (ordenados[n / 2 - 1] + ordenados[n / 2]) / 2.0
+    } else {
+        ordenados[n / 2]
+    };
+    // Desvio padrão amostral (n - 1)
+    let desvio = if n > 1 {
+        let soma_quad: f64 = ordenados.iter().map(|v| (v - media).powi(2)).sum();
+        (soma_quad / (n - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    Resumo {
+        min: ordenados[0],
+        media,
+        mediana,
+        max: ordenados[n - 1],
+        desvio,
+    }
+}
+
+/// Melhoria relativa (%) do valor final do objetivo sobre o inicial.
+pub fn melhoria_relativa(r: &Result) -> f64 {
+    if r.valor_inicial == 0.0 {
+        return 0.0;
+    }
+    (r.valor_inicial - r.valor_final) / r.valor_inicial.abs() * 100.0
+}
+
+/// Distância (%) do makespan final ao limite inferior. O limite só vale para
+/// o makespan; para os demais objetivos não há gap.
+pub fn gap_limite_inferior(r: &Result) -> Option<f64> {
+    if r.objetivo != "makespan" {
+        return None;
+    }
+    if r.limite_inferior == 0.0 {
+        return Some(0.0);
+    }
+    Some((r.valor_final - r.limite_inferior) / r.limite_inferior * 100.0)
+}
+
+/// Desvio (%) do makespan final ao melhor conhecido da instância de
+/// benchmark, quando o catálogo o registra.
+pub fn desvio_melhor_conhecido(r: &Result) -> Option<f64> {
+    if r.objetivo != "makespan" {
+        return None;
+    }
+    let melhor = r.referencia.as_ref()?.melhor_conhecido?;
+    Some((r.valor_final - melhor) / melhor * 100.0)
+}
+
+pub fn descrever_configuracao(r: &Result) -> String {
+    let mut descricao = format!("{} | n={} m={}", r.algoritmo, r.n_tarefas, r.n_maquinas);
+    if r.objetivo != "makespan" {
+        descricao.push_str(&format!(" | {}", r.objetivo));
+    }
+    if r.ambiente != "identicas" {
+        descricao.push_str(&format!(" | {}", r.ambiente));
+    }
+    if r.perturbacao > 0.0 {
+        descricao.push_str(&format!(" | perturbação={}", r.perturbacao));
+    }
+    if r.max_iteracoes > 0 {
+        descricao.push_str(&format!(" | max_iter={}", r.max_iteracoes));
+    }
+    if let Some(referencia) = &r.referencia {
+        descricao.push_str(&format!(" | {}", referencia.nome));
+    }
+    descricao
+}
+
+/// Agrupa os resultados por configuração, mantendo a ordem de primeira aparição.
+pub fn agrupar_por_configuracao(resultados: &[Result]) -> Vec<(String, Vec<&Result>)> {
+    let mut grupos: Vec<(String, Vec<&Result>)> = Vec::new();
+    for r in resultados {
+        let chave = descrever_configuracao(r);
+        match grupos.iter_mut().find(|(c, _)| *c == chave) {
+            Some((_, membros)) => membros.push(r),
+            None => grupos.push((chave, vec![r])),
+        }
+    }
+    grupos
+}
+
+pub struct Teste {
+    pub estatistica: f64,
+    pub p_valor: f64,
+}
+
+/// Postos (1 = menor valor); empates recebem a média dos postos que ocupam.
+pub fn postos(valores: &[f64]) -> Vec<f64> {
+    let mut indices: Vec<usize> = (0..valores.len()).collect();
+    indices.sort_by(|&a, &b| valores[a].total_cmp(&valores[b]));
+
+    let mut resultado = vec![0.0; valores.len()];
+    let mut i = 0;
+    while i < indices.len() {
+        let mut j = i;
+        while j + 1 < indices.len() && valores[indices[j + 1]] == valores[indices[i]] {
+            j += 1;
+        }
+        let posto_medio = (i + j) as f64 / 2.0 + 1.0;
+        for &idx in &indices[i..=j] {
+            resultado[idx] = posto_medio;
+        }
+        i = j + 1;
+    }
+    resultado
+}
+
+/// Σ(t³ - t) sobre os grupos de empates, usado nas correções das variâncias.
+fn correcao_empates(valores: &[f64]) -> f64 {
+    let mut ordenados = valores.to_vec();
+    ordenados.sort_by(|a, b| a.total_cmp(b));
+    let mut soma = 0.0;
+    let mut i = 0;
+    while i < ordenados.len() {
+        let mut j = i;
+        while j + 1 < ordenados.len() && ordenados[j + 1] == ordenados[i] {
+            j += 1;
+        }
+        let t = (j - i + 1) as f64;
+        soma += t * t * t - t;
+        i = j + 1;
+    }
+    soma
+}
+
+/// Teste de postos sinalizados de Wilcoxon (bilateral) para amostras pareadas.
+/// Retorna o teste com W = min(W+, W-) e a correlação rank-biserial pareada
+/// (W+ - W-) / (W+ + W-). Usa a distribuição exata até 25 pares sem empates.
+pub fn wilcoxon_pareado(a: &[f64], b: &[f64]) -> (Teste, f64) {
+    let diferencas: Vec<f64> = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| x - y)
+        .filter(|d| *d != 0.0)
+        .collect();
+    let n = diferencas.len();
+    if n == 0 {
+        return (
+            Teste {
+                estatistica: 0.0,
+                p_valor: 1.0,
+            },
+            0.0,
+        );
+    }
+
+    let absolutas: Vec<f64> = diferencas.iter().map(|d| d.abs()).collect();
+    let postos_abs = postos(&absolutas);
+    let w_mais: f64 = diferencas
+        .iter()
+        .zip(&postos_abs)
+        .filter(|(d, _)| **d > 0.0)
+        .map(|(_, p)| p)
+        .sum();
+    let total = (n * (n + 1)) as f64 / 2.0;
+    let w_menos = total - w_mais;
+    let w = w_mais.min(w_menos);
+    let efeito = (w_mais - w_menos) / total;
+
+    let empates = correcao_empates(&absolutas);
+    let p_valor = if empates == 0.0 && n <= 25 {
+        // Distribuição exata de W+: número de subconjuntos de {1..n} com cada soma
+        let max_soma = n * (n + 1) / 2;
+        let mut contagem = vec![0.0f64; max_soma + 1];
+        contagem[0] = 1.0;
+        for k in 1..=n {
+            for s in (k..=max_soma).rev() {
+                contagem[s] += contagem[s - k];
+            }
+        }
+        let cauda: f64 = contagem[0..=(w as usize)].iter().sum();
+        (2.0 * cauda / 2f64.powi(n as i32)).min(1.0)
+    } else {
+        let nf = n as f64;
+        let media = nf * (nf + 1.0) / 4.0;
+        let variancia = nf * (nf + 1.0) * (2.0 * nf + 1.0) / 24.0 - empates / 48.0;
+        let z = ((w - media).abs() - 0.5).max(0.0) / variancia.sqrt();
+        2.0 * (1.0 - normal_cdf(z))
+    };
+
+    (
+        Teste {
+            estatistica: w,
+            p_valor,
+        },
+        efeito,
+    )
+}
+
+/// Teste U de Mann–Whitney (bilateral, aproximação normal com correção de
+/// empates e de continuidade). Retorna também o A12 de Vargha–Delaney,
+/// P(A > B) + 0.5·P(A = B): acima de 0.5, A tende a valores maiores que B.
+pub fn mann_whitney(a: &[f64], b: &[f64]) -> (Teste, f64) {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return (
+            Teste {
+                estatistica: 0.0,
+                p_valor: 1.0,
+            },
+            0.5,
+        );
+    }
+
+    let combinados: Vec<f64> = a.iter().chain(b).copied().collect();
+    let postos_comb = postos(&combinados);
+    let r1: f64 = postos_comb[0..a.len()].iter().sum();
+    let u1 = r1 - n1 * (n1 + 1.0) / 2.0;
+    let u = u1.min(n1 * n2 - u1);
+    let a12 = u1 / (n1 * n2);
+
+    let total = n1 + n2;
+    let media = n1 * n2 / 2.0;
+    let variancia =
+        n1 * n2 / 12.0 * ((total + 1.0) - correcao_empates(&combinados) / (total * (total - 1.0)));
+    let p_valor = if variancia > 0.0 {
+        let z = ((u1 - media).abs() - 0.5).max(0.0) / variancia.sqrt();
+        2.0 * (1.0 - normal_cdf(z))
+    } else {
+        1.0
+    };
+
+    (
+        Teste {
+            estatistica: u,
+            p_valor,
+        },
+        a12,
+    )
+}
+
+/// Teste de Friedman. `blocos[i][j]` é o valor do algoritmo j no bloco i.
+/// Retorna o teste (χ² com correção de empates) e os postos médios de cada
+/// algoritmo (1 = melhor, isto é, menor valor).
+pub fn friedman(blocos: &[Vec<f64>]) -> (Teste, Vec<f64>) {
+    let n = blocos.len();
+    let k = blocos.first().map_or(0, |b| b.len());
+    if n == 0 || k < 2 {
+        return (
+            Teste {
+                estatistica: 0.0,
+                p_valor: 1.0,
+            },
+            vec![1.0; k],
+        );
+    }
+
+    let mut soma_postos = vec![0.0; k];
+    let mut empates = 0.0;
+    for bloco in blocos {
+        for (j, p) in postos(bloco).into_iter().enumerate() {
+            soma_postos[j] += p;
+        }
+        empates += correcao_empates(bloco);
+    }
+
+    let (nf, kf) = (n as f64, k as f64);
+    let postos_medios: Vec<f64> = soma_postos.iter().map(|s| s / nf).collect();
+    let soma_quad: f64 = postos_medios.iter().map(|r| r * r).sum();
+    let mut chi2 = 12.0 * nf / (kf * (kf + 1.0)) * (soma_quad - kf * (kf + 1.0).powi(2) / 4.0);
+    let divisor = 1.0 - empates / (nf * kf * (kf * kf - 1.0));
+    if divisor > 0.0 {
+        chi2 /= divisor;
+    }
+
+    (
+        Teste {
+            estatistica: chi2,
+            p_valor: qui_quadrado_sf(chi2, kf - 1.0),
+        },
+        postos_medios,
+    )
+}
+
+/// Diferença crítica do pós-teste de Nemenyi (α = 0.05) para k algoritmos e n blocos.
+pub fn diferenca_critica_nemenyi(k: usize, n: usize) -> Option<f64> {
+    const Q_005: [f64; 9] = [
+        1.960, 2.343, 2.569, 2.728, 2.850, 2.949, 3.031, 3.102, 3.164,
+    ];
+    let q = Q_005.get(k.checked_sub(2)?)?;
+    Some(q * ((k * (k + 1)) as f64 / (6.0 * n as f64)).sqrt())
+}
+
+pub fn normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z / std::f64::consts::SQRT_2)
+}
+
+/// Função erro complementar (aproximação de Chebyshev, erro relativo < 1.2e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let r = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+fn ln_gama(x: f64) -> f64 {
+    // Aproximação de Lanczos (g = 7, n = 9)
+    const COEF: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.5203681218851,
+        -1259.1392167224028,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507343278686905,
+        -0.13857109526572012,
+        9.984_369_578_019_572e-6,
+        1.5056327351493116e-7,
+    ];
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gama(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let soma = COEF[1..]
+        .iter()
+        .enumerate()
+        .fold(COEF[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + soma.ln()
+}
+
+/// Função gama incompleta regularizada superior Q(a, x).
+fn gama_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefixo = (-x + a * x.ln() - ln_gama(a)).exp();
+    if x < a + 1.0 {
+        // Série para P(a, x)
+        let mut termo = 1.0 / a;
+        let mut soma = termo;
+        let mut ap = a;
+        for _ in 0..500 {
+            ap += 1.0;
+            termo *= x / ap;
+            soma += termo;
+            if termo.abs() < soma.abs() * 1e-14 {
+                break;
+            }
+        }
+        1.0 - soma * prefixo
+    } else {
+        // Fração contínua de Lentz para Q(a, x)
+        let minimo = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / minimo;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..500 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < minimo {
+                d = minimo;
+            }
+            c = b + an / c;
+            if c.abs() < minimo {
+                c = minimo;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-14 {
+                break;
+            }
+        }
+        prefixo * h
+    }
+}
+
+/// P(X > x) para X ~ χ² com `graus` graus de liberdade.
+pub fn qui_quadrado_sf(x: f64, graus: f64) -> f64 {
+    gama_q(graus / 2.0, x / 2.0).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn perto(a: f64, b: f64, tolerancia: f64) -> bool {
+        (a - b).abs() < tolerancia
+    }
+
+    #[test]
+    fn wilcoxon_exato_sem_empates() {
+        // Diferenças 1, 2, -3, 4, 5, 6: W+ = 18, W- = 3; P(W+ ≤ 3) = 5/64 para n = 6
+        let a = [11.0, 12.0, 7.0, 14.0, 15.0, 16.0];
+        let b = [10.0; 6];
+        let (teste, efeito) = wilcoxon_pareado(&a, &b);
+        assert_eq!(teste.estatistica, 3.0);
+        assert!(perto(teste.p_valor, 10.0 / 64.0, 1e-12));
+        assert!(perto(efeito, 15.0 / 21.0, 1e-12));
+
+        // Todas as diferenças positivas com n = 5: p = 2/32
+        let (teste, efeito) = wilcoxon_pareado(&[2.0, 3.0, 4.0, 5.0, 6.0], &[1.0; 5]);
+        assert_eq!(teste.estatistica, 0.0);
+        assert!(perto(teste.p_valor, 0.0625, 1e-12));
+        assert_eq!(efeito, 1.0);
+    }
+
+    #[test]
+    fn wilcoxon_ignora_diferencas_nulas() {
+        let (teste, _) = wilcoxon_pareado(&[1.0, 2.0], &[1.0, 2.0]);
+        assert_eq!(teste.p_valor, 1.0);
+    }
+
+    #[test]
+    fn mann_whitney_aproximacao_normal() {
+        // U = 0; z = (4.5 - 0.5)/√5.25; p = 0.0809 (scipy, correção de continuidade)
+        let (teste, a12) = mann_whitney(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+        assert_eq!(teste.estatistica, 0.0);
+        assert!(perto(teste.p_valor, 0.080856, 1e-4));
+        assert_eq!(a12, 0.0);
+
+        // Amostras idênticas: sem diferença
+        let (teste, a12) = mann_whitney(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]);
+        assert!(perto(teste.p_valor, 1.0, 1e-6));
+        assert_eq!(a12, 0.5);
+    }
+
+    #[test]
+    fn friedman_ordem_constante() {
+        // 4 blocos com postos sempre 1, 2, 3: χ² = 8, p = e^-4 (2 graus de liberdade)
+        let blocos = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![10.0, 20.0, 30.0],
+            vec![5.0, 6.0, 7.0],
+            vec![0.1, 0.2, 0.3],
+        ];
+        let (teste, postos_medios) = friedman(&blocos);
+        assert!(perto(teste.estatistica, 8.0, 1e-9));
+        assert!(perto(teste.p_valor, (-4.0f64).exp(), 1e-6));
+        assert_eq!(postos_medios, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn friedman_com_empates() {
+        // Blocos inteiramente empatados não contribuem: χ² corrigido = 0 → p = 1
+        let (teste, postos_medios) = friedman(&[vec![1.0, 1.0, 1.0], vec![2.0, 2.0, 2.0]]);
+        assert_eq!(teste.estatistica, 0.0);
+        assert_eq!(teste.p_valor, 1.0);
+        assert_eq!(postos_medios, vec![2.0, 2.0, 2.0]);
+    }
+}
@@ -0,0 +1,342 @@
+use crate::analise::nome_curto;
+use crate::utils::ler_lista;
+use std::cmp::Ordering;
+use std::io;
+use std::path::Path;
+
+/// Uma linha de um CSV de resultados de sessões anteriores.
+pub struct Registro {
+    pub arquivo: String,
+    /// Todas as colunas da linha, na ordem do cabeçalho, para o detalhe.
+    pub campos: Vec<(String, String)>,
+    pub algoritmo: String,
+    pub n: usize,
+    pub m: usize,
+    pub parametro: String,
+    pub max_iter: String,
+    pub valor: f64,
+    pub tempo_ms: f64,
+    pub ambiente: String,
+    pub objetivo: String,
+    pub timestamp: String,
+}
+
+impl Registro {
+    pub fn campo(&self, nome: &str) -> Option<&str> {
+        self.campos
+            .iter()
+            .find(|(c, _)| c == nome)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Ordem {
+    Data,
+    Algoritmo,
+    N,
+    M,
+    Parametro,
+    Valor,
+    Tempo,
+}
+
+impl Ordem {
+    pub const TODAS: [Ordem; 7] = [
+        Ordem::Data,
+        Ordem::Algoritmo,
+        Ordem::N,
+        Ordem::M,
+        Ordem::Parametro,
+        Ordem::Valor,
+        Ordem::Tempo,
+    ];
+
+    pub fn nome(&self) -> &'static str {
+        match self {
+            Ordem::Data => "data",
+            Ordem::Algoritmo => "algoritmo",
+            Ordem::N => "n",
+            Ordem::M => "m",
+            Ordem::Parametro => "parâmetro",
+            Ordem::Valor => "valor",
+            Ordem::Tempo => "tempo",
+        }
+    }
+
+    fn comparar(&self, a: &Registro, b: &Registro) -> Ordering {
+        match self {
+            Ordem::Data => a.timestamp.cmp(&b.timestamp),
+            Ordem::Algoritmo => a.algoritmo.cmp(&b.algoritmo),
+            Ordem::N => a.n.cmp(&b.n),
+            Ordem::M => a.m.cmp(&b.m),
+            // Parâmetros numéricos em ordem de valor, "NA" depois deles
+            Ordem::Parametro => match (a.parametro.parse::<f64>(), b.parametro.parse::<f64>()) {
+                (Ok(x), Ok(y)) => x.total_cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.parametro.cmp(&b.parametro),
+            },
+            Ordem::Valor => a.valor.total_cmp(&b.valor),
+            Ordem::Tempo => a.tempo_ms.total_cmp(&b.tempo_ms),
+        }
+    }
+}
+
+/// Condição de um termo do filtro `campo=valor`.
+enum Condicao {
+    Algoritmo(String),
+    N(Vec<f64>),
+    M(Vec<f64>),
+    Parametro(String),
+    Ambiente(String),
+    Objetivo(String),
+    Texto(String),
+}
+
+impl Condicao {
+    fn aceita(&self, r: &Registro) -> bool {
+        let contem = |campo: &str, texto: &str| campo.to_lowercase().contains(texto);
+        match self {
+            Condicao::Algoritmo(t) => contem(&r.algoritmo, t),
+            Condicao::N(v) => v.contains(&(r.n as f64)),
+            Condicao::M(v) => v.contains(&(r.m as f64)),
+            Condicao::Parametro(t) => match (ler_lista(t), r.parametro.parse::<f64>()) {
+                (Some(v), Ok(p)) => v.iter().any(|x| (x - p).abs() < 1e-9),
+                _ => r.parametro.eq_ignore_ascii_case(t),
+            },
+            Condicao::Ambiente(t) => contem(&r.ambiente, t),
+            Condicao::Objetivo(t) => contem(&r.objetivo, t),
+            Condicao::Texto(t) => [&r.algoritmo, &r.ambiente, &r.objetivo, &r.arquivo]
+                .iter()
+                .any(|c| contem(c, t)),
+        }
+    }
+}
+
+/// Lê um filtro de termos separados por espaço, todos exigidos: `alg=ILS`,
+/// `n=100`, `m=10,20`, `p=0.2..0.5:0.1`, `amb=...`, `obj=...` ou texto livre,
+/// procurado no algoritmo, ambiente, objetivo e arquivo.
+fn ler_filtro(texto: &str) -> Result<Vec<Condicao>, String> {
+    texto
+        .split_whitespace()
+        .map(|termo| {
+            let Some((campo, valor)) = termo.split_once('=') else {
+                return Ok(Condicao::Texto(termo.to_lowercase()));
+            };
+            let lista = || ler_lista(valor).ok_or(format!("lista inválida em {termo}"));
+            match campo {
+                "alg" => Ok(Condicao::Algoritmo(valor.to_lowercase())),
+                "n" => Ok(Condicao::N(lista()?)),
+                "m" => Ok(Condicao::M(lista()?)),
+                "p" => Ok(Condicao::Parametro(valor.to_string())),
+                "amb" => Ok(Condicao::Ambiente(valor.to_lowercase())),
+                "obj" => Ok(Condicao::Objetivo(valor.to_lowercase())),
+                _ => Err(format!(
+                    "campo desconhecido: {campo} (use alg, n, m, p, amb ou obj)"
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Resultados carregados dos CSVs, com a ordenação, o filtro e a seleção da tela de histórico.
+pub struct Historico {
+    pub registros: Vec<Registro>,
+    /// Índices em `registros` que passam no filtro, na ordem exibida.
+    pub visiveis: Vec<usize>,
+    pub cursor: usize,
+    pub ordem: Ordem,
+    pub crescente: bool,
+    pub filtro: String,
+    condicoes: Vec<Condicao>,
+    /// Índices em `registros` marcados para comparação, na ordem em que foram marcados.
+    pub selecionados: Vec<usize>,
+    pub fontes: Vec<String>,
+}
+
+impl Historico {
+    pub fn new(registros: Vec<Registro>, fontes: Vec<String>) -> Self {
+        let mut historico = Historico {
+            registros,
+            visiveis: Vec::new(),
+            cursor: 0,
+            ordem: Ordem::Data,
+            crescente: false,
+            filtro: String::new(),
+            condicoes: Vec::new(),
+            selecionados: Vec::new(),
+            fontes,
+        };
+        historico.atualizar();
+        historico
+    }
+
+    /// Reaplica filtro e ordenação, mantendo o cursor no mesmo registro quando ainda visível.
+    fn atualizar(&mut self) {
+        let atual = self.atual();
+        self.visiveis = (0..self.registros.len())
+            .filter(|&i| self.condicoes.iter().all(|c| c.aceita(&self.registros[i])))
+            .collect();
+        let (ordem, crescente) = (self.ordem, self.crescente);
+        let registros = &self.registros;
+        self.visiveis.sort_by(|&a, &b| {
+            let ordenacao = ordem.comparar(&registros[a], &registros[b]);
+            if crescente {
+                ordenacao
+            } else {
+                ordenacao.reverse()
+            }
+        });
+        self.cursor = atual
+            .and_then(|i| self.visiveis.iter().position(|&v| v == i))
+            .unwrap_or(0);
+    }
+
+    /// Índice em `registros` da linha sob o cursor.
+    pub fn atual(&self) -> Option<usize> {
+        self.visiveis.get(self.cursor).copied()
+    }
+
+    pub fn mover(&mut self, passo: isize) {
+        let ultimo = self.visiveis.len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize + passo).clamp(0, ultimo) as usize;
+    }
+
+    pub fn proxima_ordem(&mut self) {
+        let i = Ordem::TODAS
+            .iter()
+            .position(|&o| o == self.ordem)
+            .unwrap_or(0);
+        self.ordem = Ordem::TODAS[(i + 1) % Ordem::TODAS.len()];
+        self.atualizar();
+    }
+
+    pub fn inverter_ordem(&mut self) {
+        self.crescente = !self.crescente;
+        self.atualizar();
+    }
+
+    /// Troca o filtro; um filtro inválido é recusado e o anterior continua valendo.
+    pub fn filtrar(&mut self, texto: &str) -> Result<(), String> {
+        self.condicoes = ler_filtro(texto)?;
+        self.filtro = texto.trim().to_string();
+        self.atualizar();
+        Ok(())
+    }
+
+    /// Marca ou desmarca a linha sob o cursor para comparação.
+    pub fn alternar_selecao(&mut self) {
+        if let Some(i) = self.atual() {
+            match self.selecionados.iter().position(|&s| s == i) {
+                Some(pos) => {
+                    self.selecionados.remove(pos);
+                }
+                None => self.selecionados.push(i),
+            }
+        }
+    }
+}
+
+/// Arquivos CSV de resultados em `caminho`: o próprio arquivo, ou os `.csv` de um
+/// diretório em ordem alfabética (convergência, frentes e análises são ignorados
+/// na leitura por não terem as colunas de resultado).
+fn arquivos_csv(caminho: &str) -> io::Result<Vec<String>> {
+    if !Path::new(caminho).is_dir() {
+        return Ok(vec![caminho.to_string()]);
+    }
+    let mut arquivos = Vec::new();
+    for entrada in std::fs::read_dir(caminho)? {
+        let entrada = entrada?.path();
+        if entrada.is_file() && entrada.extension().is_some_and(|e| e == "csv") {
+            arquivos.push(entrada.to_string_lossy().into_owned());
+        }
+    }
+    arquivos.sort();
+    Ok(arquivos)
+}
+
+/// Lê um CSV de resultados de qualquer versão do esquema. Devolve `None` se o
+/// arquivo não tiver as colunas de resultado.
+fn ler_resultados(caminho: &str) -> io::Result<Option<Vec<Registro>>> {
+    let mut leitor = csv::Reader::from_path(caminho)?;
+    let cabecalho = leitor.headers()?.clone();
+    let coluna = |nome: &str| cabecalho.iter().position(|c| c == nome);
+    let (Some(c_heur), Some(c_n), Some(c_m), Some(c_valor)) = (
+        coluna("heuristica"),
+        coluna("n"),
+        coluna("m"),
+        coluna("valor"),
+    ) else {
+        return Ok(None);
+    };
+    let c_amb = coluna("ambiente");
+
+    let mut registros = Vec::new();
+    for (i, registro) in leitor.records().enumerate() {
+        let registro = registro?;
+        let invalido = |campo: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{caminho}: linha {}: valor inválido na coluna '{campo}'",
+                    i + 2
+                ),
+            )
+        };
+        let texto = |nome: &str| coluna(nome).map_or("NA", |c| &registro[c]).to_string();
+        let ambiente = match c_amb.map(|c| &registro[c]) {
+            None | Some("NA") => "identicas".to_string(),
+            Some(v) => v.to_string(),
+        };
+        let objetivo = match texto("objetivo").as_str() {
+            "NA" => "makespan".to_string(),
+            v => v.to_string(),
+        };
+        registros.push(Registro {
+            arquivo: caminho.to_string(),
+            campos: cabecalho
+                .iter()
+                .zip(registro.iter())
+                .map(|(c, v)| (c.to_string(), v.to_string()))
+                .collect(),
+            algoritmo: nome_curto(&registro[c_heur]).to_string(),
+            n: registro[c_n].parse().map_err(|_| invalido("n"))?,
+            m: registro[c_m].parse().map_err(|_| invalido("m"))?,
+            parametro: texto("parametro"),
+            max_iter: texto("max_iter"),
+            valor: registro[c_valor].parse().map_err(|_| invalido("valor"))?,
+            tempo_ms: texto("tempo(ms)").parse().unwrap_or(f64::NAN),
+            ambiente,
+            objetivo,
+            timestamp: texto("timestamp"),
+        });
+    }
+    Ok(Some(registros))
+}
+
+/// Carrega os resultados de cada fonte (arquivo CSV ou diretório de CSVs).
+/// Fontes ausentes são ignoradas; um arquivo informado diretamente sem as
+/// colunas de resultado é um erro.
+pub fn carregar(fontes: &[String]) -> io::Result<Historico> {
+    let mut registros = Vec::new();
+    for fonte in fontes {
+        if !Path::new(fonte).exists() {
+            continue;
+        }
+        let diretorio = Path::new(fonte).is_dir();
+        for arquivo in arquivos_csv(fonte)? {
+            match ler_resultados(&arquivo)? {
+                Some(lidos) => registros.extend(lidos),
+                None if diretorio => {}
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{arquivo}: não é um CSV de resultados"),
+                    ))
+                }
+            }
+        }
+    }
+    Ok(Historico::new(registros, fontes.to_vec()))
+}
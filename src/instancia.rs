@@ -0,0 +1,1322 @@
+use crate::precedencia::Precedencias;
+use crate::utils::TarefaAlocada;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::io;
+
+/// Como os fatores de velocidade das máquinas são definidos (Q||Cmax).
+#[derive(Clone, PartialEq)]
+pub enum PerfilVelocidade {
+    /// Todas as máquinas com velocidade 1 (P||Cmax).
+    Identicas,
+    /// Metade das máquinas com velocidade 1 e a outra metade com velocidade 2.
+    DuasClasses,
+    /// Velocidades sorteadas uniformemente em [1, 4], com uma casa decimal.
+    Aleatorias,
+    /// Lista informada pelo usuário, repetida ciclicamente até m máquinas.
+    Lista(Vec<f64>),
+}
+
+impl PerfilVelocidade {
+    pub fn nome(&self) -> String {
+        match self {
+            PerfilVelocidade::Identicas => "identicas".to_string(),
+            PerfilVelocidade::DuasClasses => "duas-classes".to_string(),
+            PerfilVelocidade::Aleatorias => "aleatorias-1-4".to_string(),
+            PerfilVelocidade::Lista(v) => {
+                let valores: Vec<String> = v.iter().map(|s| s.to_string()).collect();
+                format!("lista-{}", valores.join("-"))
+            }
+        }
+    }
+
+    fn gerar(&self, m: usize, rng: &mut impl Rng) -> Vec<f64> {
+        match self {
+            PerfilVelocidade::Identicas => vec![1.0; m],
+            PerfilVelocidade::DuasClasses => (0..m)
+                .map(|i| if i < m.div_ceil(2) { 1.0 } else { 2.0 })
+                .collect(),
+            PerfilVelocidade::Aleatorias => (0..m)
+                .map(|_| (rng.gen_range(1.0..=4.0f64) * 10.0).round() / 10.0)
+                .collect(),
+            PerfilVelocidade::Lista(v) if !v.is_empty() => (0..m).map(|i| v[i % v.len()]).collect(),
+            PerfilVelocidade::Lista(_) => vec![1.0; m],
+        }
+    }
+}
+
+/// Geradores de matriz de tempos p_ij para máquinas não relacionadas (R||Cmax),
+/// nas classes usuais da literatura (ex.: Fanjul-Peyro & Ruiz, 2010). U[1, 100]
+/// abaixo é a distribuição padrão; com outra, ela toma o seu lugar.
+#[derive(Clone, PartialEq)]
+pub enum TipoMatriz {
+    /// p_ij ~ U[1, 100].
+    NaoCorrelacionada,
+    /// b_i ~ U[1, 100] por máquina e p_ij ~ U[b_i + 1, b_i + 20].
+    CorrelacionadaMaquina,
+    /// a_j ~ U[1, 100] por tarefa e p_ij ~ U[a_j + 1, a_j + 20].
+    CorrelacionadaTarefa,
+}
+
+impl TipoMatriz {
+    pub fn nome(&self) -> &'static str {
+        match self {
+            TipoMatriz::NaoCorrelacionada => "matriz-nao-correlacionada",
+            TipoMatriz::CorrelacionadaMaquina => "matriz-correlacionada-maquina",
+            TipoMatriz::CorrelacionadaTarefa => "matriz-correlacionada-tarefa",
+        }
+    }
+
+    fn gerar(
+        &self,
+        n: usize,
+        m: usize,
+        distribuicao: &Distribuicao,
+        rng: &mut impl Rng,
+    ) -> Vec<Vec<u32>> {
+        match self {
+            TipoMatriz::NaoCorrelacionada => (0..n)
+                .map(|_| (0..m).map(|_| distribuicao.sortear(rng)).collect())
+                .collect(),
+            TipoMatriz::CorrelacionadaMaquina => {
+                let b: Vec<u32> = (0..m).map(|_| distribuicao.sortear(rng)).collect();
+                (0..n)
+                    .map(|_| {
+                        b.iter()
+                            .map(|&bi| rng.gen_range(bi.saturating_add(1)..=bi.saturating_add(20)))
+                            .collect()
+                    })
+                    .collect()
+            }
+            TipoMatriz::CorrelacionadaTarefa => (0..n)
+                .map(|_| {
+                    let a = distribuicao.sortear(rng);
+                    (0..m)
+                        .map(|_| rng.gen_range(a.saturating_add(1)..=a.saturating_add(20)))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Classes de instâncias de P||Cmax da literatura, todas uniformes:
+/// França et al. (1994) usam U[1, 100], U[20, 100] e U[50, 100];
+/// Dell'Amico & Martello (1995) acrescentam U[1, 1000] e U[1, 10000].
+pub const CLASSES_LITERATURA: [(u32, u32); 5] =
+    [(1, 100), (20, 100), (50, 100), (1, 1000), (1, 10000)];
+
+/// Distribuição dos tempos de processamento das instâncias geradas. Os
+/// valores sorteados são arredondados para inteiros de no mínimo 1.
+#[derive(Clone, PartialEq)]
+pub enum Distribuicao {
+    /// p_j ~ U[min, max]; U[1, 100] é o padrão.
+    Uniforme { min: u32, max: u32 },
+    /// p_j ~ N(média, desvio²).
+    Normal { media: f64, desvio: f64 },
+    /// p_j ~ Exp com a média dada.
+    Exponencial { media: f64 },
+    /// ln p_j ~ N(mu, sigma²).
+    LogNormal { mu: f64, sigma: f64 },
+    /// Mistura de duas uniformes: com probabilidade `fracao_longas` a tarefa
+    /// vem de `longas`, senão de `curtas`.
+    Bimodal {
+        curtas: (u32, u32),
+        longas: (u32, u32),
+        fracao_longas: f64,
+    },
+    /// Classe k (1 a 5) de `CLASSES_LITERATURA`.
+    Classe(usize),
+}
+
+impl Distribuicao {
+    pub const PADRAO: Distribuicao = Distribuicao::Uniforme { min: 1, max: 100 };
+
+    pub fn nome(&self) -> String {
+        match self {
+            Distribuicao::Uniforme { min, max } => format!("u{min}-{max}"),
+            Distribuicao::Normal { media, desvio } => format!("normal-{media}-{desvio}"),
+            Distribuicao::Exponencial { media } => format!("exponencial-{media}"),
+            Distribuicao::LogNormal { mu, sigma } => format!("lognormal-{mu}-{sigma}"),
+            Distribuicao::Bimodal {
+                curtas,
+                longas,
+                fracao_longas,
+            } => format!(
+                "bimodal-u{}-{}-u{}-{}-f{fracao_longas}",
+                curtas.0, curtas.1, longas.0, longas.1
+            ),
+            Distribuicao::Classe(k) => {
+                let (min, max) = CLASSES_LITERATURA[k - 1];
+                format!("classe{k}-u{min}-{max}")
+            }
+        }
+    }
+
+    /// Lê "uniforme:a,b", "normal:média,desvio", "exponencial:média",
+    /// "lognormal:mu,sigma", "bimodal:a1,b1,a2,b2,fração" ou "classe:k".
+    pub fn ler(texto: &str) -> Option<Self> {
+        let (tipo, parametros) = texto.split_once(':')?;
+        let valores: Vec<f64> = parametros
+            .split(',')
+            .map(|v| v.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        let inteiro = |v: f64| (v >= 1.0 && v.fract() == 0.0).then_some(v as u32);
+        let faixa = |a: f64, b: f64| Some((inteiro(a)?, inteiro(b)?)).filter(|(a, b)| a <= b);
+        let distribuicao = match (tipo, &valores[..]) {
+            ("uniforme", &[a, b]) => {
+                let (min, max) = faixa(a, b)?;
+                Distribuicao::Uniforme { min, max }
+            }
+            ("normal", &[media, desvio]) if desvio >= 0.0 => Distribuicao::Normal { media, desvio },
+            ("exponencial", &[media]) if media > 0.0 => Distribuicao::Exponencial { media },
+            ("lognormal", &[mu, sigma]) if sigma >= 0.0 => Distribuicao::LogNormal { mu, sigma },
+            ("bimodal", &[a1, b1, a2, b2, f]) if (0.0..=1.0).contains(&f) => {
+                Distribuicao::Bimodal {
+                    curtas: faixa(a1, b1)?,
+                    longas: faixa(a2, b2)?,
+                    fracao_longas: f,
+                }
+            }
+            ("classe", &[k]) => {
+                let k = inteiro(k).filter(|&k| k as usize <= CLASSES_LITERATURA.len())?;
+                Distribuicao::Classe(k as usize)
+            }
+            _ => return None,
+        };
+        Some(distribuicao)
+    }
+
+    pub fn sortear(&self, rng: &mut impl Rng) -> u32 {
+        let arredondar = |x: f64| x.round().clamp(1.0, u32::MAX as f64) as u32;
+        match self {
+            Distribuicao::Uniforme { min, max } => rng.gen_range(*min..=*max),
+            Distribuicao::Normal { media, desvio } => {
+                arredondar(media + desvio * normal_padrao(rng))
+            }
+            Distribuicao::Exponencial { media } => {
+                // Inversa da acumulada, com u em (0, 1]
+                let u: f64 = 1.0 - rng.gen::<f64>();
+                arredondar(-media * u.ln())
+            }
+            Distribuicao::LogNormal { mu, sigma } => {
+                arredondar((mu + sigma * normal_padrao(rng)).exp())
+            }
+            Distribuicao::Bimodal {
+                curtas,
+                longas,
+                fracao_longas,
+            } => {
+                let (min, max) = if rng.gen_bool(*fracao_longas) {
+                    *longas
+                } else {
+                    *curtas
+                };
+                rng.gen_range(min..=max)
+            }
+            Distribuicao::Classe(k) => {
+                let (min, max) = CLASSES_LITERATURA[k - 1];
+                rng.gen_range(min..=max)
+            }
+        }
+    }
+}
+
+/// Normal padrão pelo método de Box-Muller.
+fn normal_padrao(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Ambiente de máquinas das instâncias geradas.
+#[derive(Clone, PartialEq)]
+pub enum Ambiente {
+    /// Máquinas uniformes; com velocidades idênticas é o P||Cmax original.
+    Uniformes(PerfilVelocidade),
+    NaoRelacionadas(TipoMatriz),
+}
+
+impl Ambiente {
+    pub fn nome(&self) -> String {
+        match self {
+            Ambiente::Uniformes(perfil) => perfil.nome(),
+            Ambiente::NaoRelacionadas(tipo) => tipo.nome().to_string(),
+        }
+    }
+
+    /// Ambientes oferecidos no menu, na ordem em que a tecla V os percorre.
+    pub fn padroes() -> Vec<Ambiente> {
+        vec![
+            Ambiente::Uniformes(PerfilVelocidade::Identicas),
+            Ambiente::Uniformes(PerfilVelocidade::DuasClasses),
+            Ambiente::Uniformes(PerfilVelocidade::Aleatorias),
+            Ambiente::NaoRelacionadas(TipoMatriz::NaoCorrelacionada),
+            Ambiente::NaoRelacionadas(TipoMatriz::CorrelacionadaMaquina),
+            Ambiente::NaoRelacionadas(TipoMatriz::CorrelacionadaTarefa),
+        ]
+    }
+}
+
+/// Restrições de elegibilidade: em quais máquinas cada tarefa pode ser processada.
+#[derive(Clone, PartialEq)]
+pub enum Elegibilidade {
+    /// Toda tarefa pode ir para qualquer máquina.
+    Todas,
+    /// Cada tarefa é elegível em uma fração sorteada das máquinas (ao menos uma).
+    Aleatoria(f64),
+    /// Conjuntos (tarefa, máquinas) lidos de arquivo; tarefas não listadas
+    /// são elegíveis em todas as máquinas.
+    Arquivo(String, Vec<(usize, Vec<usize>)>),
+}
+
+impl Elegibilidade {
+    pub fn nome(&self) -> String {
+        match self {
+            Elegibilidade::Todas => "todas".to_string(),
+            Elegibilidade::Aleatoria(fracao) => format!("eleg-{fracao}"),
+            Elegibilidade::Arquivo(..) => "eleg-arquivo".to_string(),
+        }
+    }
+
+    /// Lê um arquivo com uma linha "tarefa: m1 m2 ..." por tarefa restrita.
+    /// Linhas iniciadas por '#' são ignoradas.
+    pub fn ler(caminho: &str) -> io::Result<Self> {
+        let conteudo = std::fs::read_to_string(caminho)?;
+        let invalido =
+            |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{caminho}: {msg}"));
+
+        let conjuntos = conteudo
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(|linha| {
+                let (tarefa, maquinas) = linha
+                    .split_once(':')
+                    .ok_or_else(|| invalido(format!("esperado \"tarefa: máquinas\": {linha}")))?;
+                let tarefa = tarefa
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalido(format!("tarefa inválida: {tarefa}")))?;
+                let maquinas = maquinas
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|v| !v.is_empty())
+                    .map(|v| {
+                        v.parse()
+                            .map_err(|_| invalido(format!("máquina inválida: {v}")))
+                    })
+                    .collect::<io::Result<_>>()?;
+                Ok((tarefa, maquinas))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Elegibilidade::Arquivo(caminho.to_string(), conjuntos))
+    }
+}
+
+/// Tempos de preparação dependentes da sequência: cada tarefa pertence a uma
+/// família e trocar da família f para a g numa máquina custa s[f][g].
+#[derive(Clone, PartialEq)]
+pub enum Setup {
+    Nenhum,
+    /// k famílias sorteadas, com s[f][g] ~ U[1, 25] para f ≠ g e zero na diagonal.
+    Aleatorio(usize),
+    /// Matriz k × k lida de arquivo; as famílias das tarefas são sorteadas.
+    Arquivo(String, Vec<Vec<u32>>),
+}
+
+impl Setup {
+    pub fn nome(&self) -> String {
+        match self {
+            Setup::Nenhum => "sem-setup".to_string(),
+            Setup::Aleatorio(k) => format!("setup-k{k}"),
+            Setup::Arquivo(..) => "setup-arquivo".to_string(),
+        }
+    }
+
+    /// Lê uma matriz de setup em texto: a primeira linha traz k e as k linhas
+    /// seguintes os tempos s[f][g]. Linhas iniciadas por '#' são ignoradas.
+    pub fn ler(caminho: &str) -> io::Result<Self> {
+        let conteudo = std::fs::read_to_string(caminho)?;
+        let invalido =
+            |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{caminho}: {msg}"));
+
+        let mut linhas = conteudo
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        let k: usize = linhas
+            .next()
+            .ok_or_else(|| invalido("arquivo vazio".to_string()))?
+            .parse()
+            .map_err(|_| invalido("o cabeçalho deve conter o número de famílias".to_string()))?;
+        let matriz: Vec<Vec<u32>> = linhas
+            .map(|linha| {
+                linha
+                    .split_whitespace()
+                    .map(|v| {
+                        v.parse()
+                            .map_err(|_| invalido(format!("tempo inválido: {v}")))
+                    })
+                    .collect::<io::Result<Vec<u32>>>()
+            })
+            .collect::<io::Result<_>>()?;
+        if k == 0 || matriz.len() != k || matriz.iter().any(|linha| linha.len() != k) {
+            return Err(invalido(format!("esperada uma matriz {k} × {k}")));
+        }
+
+        Ok(Setup::Arquivo(caminho.to_string(), matriz))
+    }
+}
+
+/// Como datas de liberação, datas de entrega e pesos das tarefas são gerados.
+#[derive(Clone, PartialEq)]
+pub enum Prazos {
+    /// r_j = 0, d_j = 0 e w_j = 1: os objetivos de atraso se reduzem aos de conclusão.
+    Nenhum,
+    /// Esquema de Potts & Van Wassenhove (1982): com P = Σp / m, r_j ~ U[0, P/2],
+    /// d_j ~ U[P(1 - tf - rdd/2), P(1 - tf + rdd/2)] (e nunca antes de r_j + p_j)
+    /// e w_j ~ U[1, 10]. `tf` controla o aperto e `rdd` a dispersão das entregas.
+    Aleatorios { tf: f64, rdd: f64 },
+}
+
+impl Prazos {
+    pub fn nome(&self) -> String {
+        match self {
+            Prazos::Nenhum => "sem-prazos".to_string(),
+            Prazos::Aleatorios { tf, rdd } => format!("prazos-tf{tf}-rdd{rdd}"),
+        }
+    }
+}
+
+/// Data de liberação, data de entrega e peso de cada tarefa.
+#[derive(Clone)]
+pub struct DatasTarefas {
+    pub liberacao: Vec<u32>,
+    pub entrega: Vec<u32>,
+    pub pesos: Vec<u32>,
+}
+
+/// Famílias das tarefas e matriz de setup entre famílias de uma instância.
+#[derive(Clone)]
+pub struct TemposSetup {
+    pub familias: Vec<usize>,
+    pub matriz: Vec<Vec<u32>>,
+}
+
+/// Identificação de uma instância lida de arquivo e, se constar do catálogo,
+/// o melhor makespan conhecido para ela.
+#[derive(Clone)]
+pub struct Referencia {
+    pub nome: String,
+    pub melhor_conhecido: Option<f64>,
+    /// Se o melhor conhecido foi provado ótimo.
+    pub otimo: bool,
+}
+
+/// Escala em uso na produção, ponto de partida da reprogramação.
+#[derive(Clone)]
+pub struct Reprogramacao {
+    pub caminho: String,
+    /// Tarefas de cada máquina, na ordem de processamento.
+    pub sequencias: Vec<Vec<usize>>,
+    /// Máximo de tarefas fora da máquina original; `None` não limita.
+    pub orcamento: Option<usize>,
+    /// Acréscimo ao objetivo por tarefa migrada.
+    pub penalidade: f64,
+    /// Máquina original de cada tarefa, preenchida por `Instancia::com_reprogramacao`.
+    pub maquina_original: Vec<usize>,
+}
+
+impl Reprogramacao {
+    pub fn nome(&self) -> String {
+        let mut nome = "reprogramacao".to_string();
+        if let Some(k) = self.orcamento {
+            nome.push_str(&format!("-max{k}"));
+        }
+        if self.penalidade > 0.0 {
+            nome.push_str(&format!("-pen{}", self.penalidade));
+        }
+        nome
+    }
+
+    /// Lê a escala atual: uma linha "máquina: t1 t2 ..." por máquina, com as
+    /// tarefas na ordem de processamento. Máquinas não listadas ficam vazias.
+    /// Linhas iniciadas por '#' são ignoradas.
+    pub fn ler(caminho: &str) -> io::Result<Self> {
+        let conteudo = std::fs::read_to_string(caminho)?;
+        let invalido =
+            |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{caminho}: {msg}"));
+
+        let mut sequencias: Vec<Vec<usize>> = Vec::new();
+        for linha in conteudo
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        {
+            let (maquina, tarefas) = linha
+                .split_once(':')
+                .ok_or_else(|| invalido(format!("esperado \"máquina: tarefas\": {linha}")))?;
+            let maquina: usize = maquina
+                .trim()
+                .parse()
+                .map_err(|_| invalido(format!("máquina inválida: {maquina}")))?;
+            let tarefas: Vec<usize> = tarefas
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|v| !v.is_empty())
+                .map(|v| {
+                    v.parse()
+                        .map_err(|_| invalido(format!("tarefa inválida: {v}")))
+                })
+                .collect::<io::Result<_>>()?;
+            if sequencias.len() <= maquina {
+                sequencias.resize(maquina + 1, Vec::new());
+            }
+            sequencias[maquina].extend(tarefas);
+        }
+
+        Ok(Reprogramacao {
+            caminho: caminho.to_string(),
+            sequencias,
+            orcamento: None,
+            penalidade: 0.0,
+            maquina_original: Vec::new(),
+        })
+    }
+}
+
+/// Converte "1,2,4.5" em uma lista de velocidades positivas.
+pub fn ler_velocidades(texto: &str) -> Option<Vec<f64>> {
+    let velocidades: Vec<f64> = texto
+        .split(',')
+        .map(|v| v.trim().parse::<f64>().ok().filter(|s| *s > 0.0))
+        .collect::<Option<_>>()?;
+    (!velocidades.is_empty()).then_some(velocidades)
+}
+
+#[derive(Clone)]
+pub struct Instancia {
+    /// Tempo de processamento de cada tarefa em uma máquina de velocidade 1.
+    /// Em instâncias com matriz, guarda o menor tempo da tarefa entre as máquinas.
+    pub tempos: Vec<u32>,
+    /// Fator de velocidade de cada máquina; a tarefa j leva tempos[j] / velocidades[i].
+    pub velocidades: Vec<f64>,
+    /// Matriz p_ij (tarefa × máquina) do R||Cmax; quando presente, substitui
+    /// `tempos` e `velocidades` no cálculo das durações.
+    pub matriz: Option<Vec<Vec<u32>>>,
+    /// Máquinas em que cada tarefa pode ser processada; `None` libera todas.
+    pub elegiveis: Option<Vec<Vec<usize>>>,
+    /// Setup dependente da sequência; `None` quando a ordem na máquina não importa.
+    pub setup: Option<TemposSetup>,
+    /// Liberação, entrega e peso das tarefas; `None` equivale a r = 0, d = 0 e w = 1.
+    pub datas: Option<DatasTarefas>,
+    /// Tarefas que precisam terminar antes de cada tarefa começar.
+    pub precedencias: Option<Precedencias>,
+    /// Escala atual da qual as buscas partem, com limite e penalidade de migrações.
+    pub reprogramacao: Option<Reprogramacao>,
+    /// Nome e melhor makespan conhecido, nas instâncias lidas de arquivo.
+    pub referencia: Option<Referencia>,
+    pub ambiente: String,
+}
+
+impl Instancia {
+    /// Gera n tarefas em m máquinas do ambiente, com tempos (ou a base da
+    /// matriz) sorteados da distribuição. Fora do padrão U[1, 100], o nome da
+    /// distribuição entra no ambiente.
+    pub fn gerar(
+        n: usize,
+        m: usize,
+        ambiente: &Ambiente,
+        distribuicao: &Distribuicao,
+        semente: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(semente);
+        let mut nome = ambiente.nome();
+        if *distribuicao != Distribuicao::PADRAO {
+            nome = format!("{nome}+{}", distribuicao.nome());
+        }
+        match ambiente {
+            Ambiente::Uniformes(perfil) => {
+                let tempos = (0..n).map(|_| distribuicao.sortear(&mut rng)).collect();
+                let velocidades = perfil.gerar(m, &mut rng);
+                Instancia::com_tempos(tempos, velocidades, nome)
+            }
+            Ambiente::NaoRelacionadas(tipo) => {
+                Instancia::com_matriz(tipo.gerar(n, m, distribuicao, &mut rng), nome)
+            }
+        }
+    }
+
+    /// Instância formada pelas tarefas recebidas no modo online: tempos e
+    /// instantes de chegada (como datas de liberação) em máquinas uniformes.
+    pub fn de_chegadas(tempos: Vec<u32>, chegadas: Vec<u32>, velocidades: Vec<f64>) -> Self {
+        let n = tempos.len();
+        let ambiente = if velocidades.iter().all(|&v| v == 1.0) {
+            "online"
+        } else {
+            "online-uniformes"
+        };
+        Instancia {
+            datas: Some(DatasTarefas {
+                liberacao: chegadas,
+                entrega: vec![0; n],
+                pesos: vec![1; n],
+            }),
+            ..Instancia::com_tempos(tempos, velocidades, ambiente.to_string())
+        }
+    }
+
+    fn com_tempos(tempos: Vec<u32>, velocidades: Vec<f64>, ambiente: String) -> Self {
+        Instancia {
+            tempos,
+            velocidades,
+            matriz: None,
+            elegiveis: None,
+            setup: None,
+            datas: None,
+            precedencias: None,
+            reprogramacao: None,
+            referencia: None,
+            ambiente,
+        }
+    }
+
+    fn com_matriz(matriz: Vec<Vec<u32>>, ambiente: String) -> Self {
+        let m = matriz.first().map_or(0, |linha| linha.len());
+        let tempos = matriz
+            .iter()
+            .map(|linha| linha.iter().copied().min().unwrap_or(0))
+            .collect();
+        Instancia {
+            matriz: Some(matriz),
+            ..Instancia::com_tempos(tempos, vec![1.0; m], ambiente)
+        }
+    }
+
+    /// Lê uma instância de benchmark da literatura, reconhecendo o formato
+    /// pela quantidade de valores após "n m" (quebras de linha são
+    /// indiferentes; linhas iniciadas por '#' são ignoradas):
+    /// - n tempos: P||Cmax (França et al.; Dell'Amico & Martello);
+    /// - n·m tempos: matriz p_ij, tarefa a tarefa, como em `ler_matriz`;
+    /// - 2·n·m valores: R||Cmax no formato de Fanjul-Peyro & Ruiz, com os
+    ///   pares "máquina tempo" de cada tarefa.
+    pub fn ler_benchmark(caminho: &str) -> io::Result<Self> {
+        let conteudo = std::fs::read_to_string(caminho)?;
+        let invalido =
+            |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{caminho}: {msg}"));
+
+        let valores: Vec<u32> = conteudo
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.starts_with('#'))
+            .flat_map(str::split_whitespace)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| invalido(format!("valor inválido: {v}")))
+            })
+            .collect::<io::Result<_>>()?;
+        let [n, m, ref resto @ ..] = valores[..] else {
+            return Err(invalido("o arquivo deve começar com \"n m\"".to_string()));
+        };
+        let (n, m) = (n as usize, m as usize);
+        if n == 0 || m == 0 {
+            return Err(invalido(format!("instância vazia (n={n}, m={m})")));
+        }
+
+        if resto.len() == n {
+            return Ok(Instancia::com_tempos(
+                resto.to_vec(),
+                vec![1.0; m],
+                "benchmark-identicas".to_string(),
+            ));
+        }
+        let matriz: Vec<Vec<u32>> = if resto.len() == n * m {
+            resto.chunks(m).map(<[u32]>::to_vec).collect()
+        } else if resto.len() == 2 * n * m {
+            resto
+                .chunks(2 * m)
+                .enumerate()
+                .map(|(j, pares)| {
+                    let mut tempos = vec![None; m];
+                    for par in pares.chunks(2) {
+                        let (i, p) = (par[0] as usize, par[1]);
+                        match tempos.get_mut(i) {
+                            Some(t @ None) => *t = Some(p),
+                            _ => {
+                                return Err(invalido(format!(
+                                    "tarefa {j}: máquina {i} inexistente ou repetida"
+                                )))
+                            }
+                        }
+                    }
+                    Ok(tempos.into_iter().flatten().collect())
+                })
+                .collect::<io::Result<_>>()?
+        } else {
+            return Err(invalido(format!(
+                "formato não reconhecido: após \"n m\" são esperados {n}, {} ou {} valores, lidos {}",
+                n * m,
+                2 * n * m,
+                resto.len()
+            )));
+        };
+        Ok(Instancia::com_matriz(
+            matriz,
+            "benchmark-matriz".to_string(),
+        ))
+    }
+
+    /// Lê uma matriz de tempos em texto: a primeira linha traz "n m" e as n
+    /// linhas seguintes os m tempos de cada tarefa. Linhas iniciadas por '#'
+    /// são ignoradas.
+    pub fn ler_matriz(caminho: &str) -> io::Result<Self> {
+        let conteudo = std::fs::read_to_string(caminho)?;
+        let invalido =
+            |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{caminho}: {msg}"));
+
+        let mut linhas = conteudo
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        let cabecalho: Vec<usize> = linhas
+            .next()
+            .ok_or_else(|| invalido("arquivo vazio".to_string()))?
+            .split_whitespace()
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| invalido(format!("cabeçalho inválido: {v}")))
+            })
+            .collect::<io::Result<_>>()?;
+        let [n, m] = cabecalho[..] else {
+            return Err(invalido("o cabeçalho deve conter \"n m\"".to_string()));
+        };
+        if n == 0 || m == 0 {
+            return Err(invalido(format!("instância vazia (n={n}, m={m})")));
+        }
+
+        let matriz: Vec<Vec<u32>> = linhas
+            .enumerate()
+            .map(|(j, linha)| {
+                let tempos: Vec<u32> = linha
+                    .split_whitespace()
+                    .map(|v| {
+                        v.parse()
+                            .map_err(|_| invalido(format!("tempo inválido: {v}")))
+                    })
+                    .collect::<io::Result<_>>()?;
+                if tempos.len() != m {
+                    return Err(invalido(format!(
+                        "tarefa {j} tem {} tempos, esperado {m}",
+                        tempos.len()
+                    )));
+                }
+                Ok(tempos)
+            })
+            .collect::<io::Result<_>>()?;
+        if matriz.len() != n || m == 0 {
+            return Err(invalido(format!(
+                "esperadas {n} tarefas em {m} máquinas, lidas {}",
+                matriz.len()
+            )));
+        }
+
+        Ok(Instancia::com_matriz(matriz, "matriz-arquivo".to_string()))
+    }
+
+    /// Sorteia as famílias das tarefas e associa a matriz de setup à instância.
+    pub fn com_setup(mut self, setup: &Setup, semente: u64) -> Self {
+        // Fluxo separado do usado para os tempos, para não correlacioná-los
+        let mut rng = StdRng::seed_from_u64(semente ^ 0xD1B5_4A32_D192_ED03);
+        let matriz = match setup {
+            Setup::Nenhum => return self,
+            Setup::Aleatorio(k) => (0..*k)
+                .map(|f| {
+                    (0..*k)
+                        .map(|g| if f == g { 0 } else { rng.gen_range(1..=25) })
+                        .collect()
+                })
+                .collect(),
+            Setup::Arquivo(_, matriz) => matriz.clone(),
+        };
+        let familias = (0..self.n_tarefas())
+            .map(|_| rng.gen_range(0..matriz.len()))
+            .collect();
+        self.setup = Some(TemposSetup { familias, matriz });
+        self.ambiente = format!("{}+{}", self.ambiente, setup.nome());
+        self
+    }
+
+    /// Sorteia liberação, entrega e peso das tarefas.
+    pub fn com_prazos(mut self, prazos: &Prazos, semente: u64) -> Self {
+        let Prazos::Aleatorios { tf, rdd } = *prazos else {
+            return self;
+        };
+        // Fluxo separado do usado para os tempos, para não correlacioná-los
+        let mut rng = StdRng::seed_from_u64(semente ^ 0x94D0_49BB_1331_11EB);
+        let p =
+            self.tempos.iter().map(|&t| t as f64).sum::<f64>() / self.n_maquinas().max(1) as f64;
+        // Com rdd < 0 os extremos se invertem; valores não finitos viram 0, de
+        // modo que nenhum par tf/rdd produz um intervalo inválido para o sorteio
+        let extremo = |x: f64| if x.is_finite() { x.max(0.0) } else { 0.0 };
+        let (a, b) = (
+            extremo(p * (1.0 - tf - rdd / 2.0)),
+            extremo(p * (1.0 - tf + rdd / 2.0)),
+        );
+        let (d_min, d_max) = (a.min(b), a.max(b));
+
+        let mut datas = DatasTarefas {
+            liberacao: Vec::new(),
+            entrega: Vec::new(),
+            pesos: Vec::new(),
+        };
+        for &tempo in &self.tempos {
+            let liberacao = rng.gen_range(0.0..=p / 2.0).round() as u32;
+            let entrega = rng.gen_range(d_min..=d_max).round() as u32;
+            datas.liberacao.push(liberacao);
+            datas.entrega.push(entrega.max(liberacao + tempo));
+            datas.pesos.push(rng.gen_range(1..=10));
+        }
+        self.datas = Some(datas);
+        self.ambiente = format!("{}+{}", self.ambiente, prazos.nome());
+        self
+    }
+
+    /// Associa o grafo de precedências (arcos "a antes de b") à instância.
+    /// Falha se algum arco citar tarefa inexistente ou formar ciclo.
+    pub fn com_precedencias(mut self, arcos: &[(usize, usize)]) -> io::Result<Self> {
+        let grafo = Precedencias::new(self.n_tarefas(), arcos)
+            .map_err(|msg| io::Error::new(io::ErrorKind::InvalidInput, msg))?;
+        self.precedencias = Some(grafo);
+        self.ambiente = format!("{}+precedencias", self.ambiente);
+        Ok(self)
+    }
+
+    /// Associa a escala atual à instância. Falha se ela não contiver cada
+    /// tarefa exatamente uma vez, usar máquina inexistente ou inelegível, ou
+    /// violar as precedências.
+    pub fn com_reprogramacao(mut self, reprogramacao: &Reprogramacao) -> io::Result<Self> {
+        let (n, m) = (self.n_tarefas(), self.n_maquinas());
+        let caminho = &reprogramacao.caminho;
+        let inviavel =
+            |msg: String| io::Error::new(io::ErrorKind::InvalidInput, format!("{caminho}: {msg}"));
+        if reprogramacao.sequencias.len() > m {
+            return Err(inviavel(format!(
+                "máquina {} não existe (m={m})",
+                reprogramacao.sequencias.len() - 1
+            )));
+        }
+
+        let mut maquina_original = vec![usize::MAX; n];
+        for (maquina, tarefas) in reprogramacao.sequencias.iter().enumerate() {
+            for &tarefa in tarefas {
+                if tarefa >= n {
+                    return Err(inviavel(format!("tarefa {tarefa} não existe (n={n})")));
+                }
+                if maquina_original[tarefa] != usize::MAX {
+                    return Err(inviavel(format!("tarefa {tarefa} aparece mais de uma vez")));
+                }
+                if !self.elegivel(tarefa, maquina) {
+                    return Err(inviavel(format!(
+                        "tarefa {tarefa} está na máquina {maquina}, que não é elegível"
+                    )));
+                }
+                maquina_original[tarefa] = maquina;
+            }
+        }
+        if let Some(tarefa) = maquina_original.iter().position(|&i| i == usize::MAX) {
+            return Err(inviavel(format!("tarefa {tarefa} não está na escala")));
+        }
+        let mut sequencias = reprogramacao.sequencias.clone();
+        sequencias.resize(m, Vec::new());
+        let referencias: Vec<&[usize]> = sequencias.iter().map(Vec::as_slice).collect();
+        if self.precedencias.is_some() && self.programar(&referencias).is_none() {
+            return Err(inviavel(
+                "a ordem das máquinas viola as precedências".to_string(),
+            ));
+        }
+
+        self.reprogramacao = Some(Reprogramacao {
+            sequencias,
+            maquina_original,
+            ..reprogramacao.clone()
+        });
+        self.ambiente = format!("{}+{}", self.ambiente, reprogramacao.nome());
+        Ok(self)
+    }
+
+    /// Se a tarefa na máquina está fora da máquina da escala atual.
+    pub fn migrou(&self, tarefa: usize, maquina: usize) -> bool {
+        self.reprogramacao
+            .as_ref()
+            .is_some_and(|r| r.maquina_original[tarefa] != maquina)
+    }
+
+    /// Quantas tarefas a alocação tira da máquina original (`None` sem escala atual).
+    pub fn migracoes(&self, alocacao: &[Vec<TarefaAlocada>]) -> Option<usize> {
+        self.reprogramacao.as_ref()?;
+        Some(
+            alocacao
+                .iter()
+                .enumerate()
+                .map(|(i, tarefas)| tarefas.iter().filter(|t| self.migrou(t.tarefa, i)).count())
+                .sum(),
+        )
+    }
+
+    /// Valor usado pelas buscas: o objetivo mais a penalidade das migrações,
+    /// ou infinito se o orçamento de migrações for estourado.
+    pub fn penalizar(&self, valor: f64, migracoes: usize) -> f64 {
+        match &self.reprogramacao {
+            Some(r) if r.orcamento.is_some_and(|k| migracoes > k) => f64::INFINITY,
+            Some(r) => valor + r.penalidade * migracoes as f64,
+            None => valor,
+        }
+    }
+
+    pub fn liberacao(&self, tarefa: usize) -> f64 {
+        self.datas
+            .as_ref()
+            .map_or(0.0, |d| d.liberacao[tarefa] as f64)
+    }
+
+    pub fn entrega(&self, tarefa: usize) -> f64 {
+        self.datas
+            .as_ref()
+            .map_or(0.0, |d| d.entrega[tarefa] as f64)
+    }
+
+    pub fn peso(&self, tarefa: usize) -> f64 {
+        self.datas.as_ref().map_or(1.0, |d| d.pesos[tarefa] as f64)
+    }
+
+    /// Início e fim de cada tarefa da sequência na máquina: a tarefa começa
+    /// após o término da anterior e do setup, mas nunca antes da sua liberação.
+    pub fn sequenciar<'a, I>(
+        &'a self,
+        maquina: usize,
+        tarefas: I,
+    ) -> impl Iterator<Item = TarefaAlocada> + 'a
+    where
+        I: IntoIterator<Item = usize>,
+        I::IntoIter: 'a,
+    {
+        let mut fim_anterior = 0.0;
+        let mut anterior = None;
+        tarefas.into_iter().map(move |tarefa| {
+            let inicio = (fim_anterior + self.setup(anterior, tarefa)).max(self.liberacao(tarefa));
+            let fim = inicio + self.duracao(tarefa, maquina);
+            fim_anterior = fim;
+            anterior = Some(tarefa);
+            TarefaAlocada {
+                tarefa,
+                inicio,
+                fim,
+            }
+        })
+    }
+
+    /// Programa todas as máquinas juntas, respeitando a ordem de cada uma: a
+    /// tarefa começa após a anterior e o setup, a liberação e o término de
+    /// todos os seus predecessores. Retorna `None` se as ordens das máquinas
+    /// se bloqueiam mutuamente (uma tarefa esperando outra que está atrás
+    /// dela, direta ou indiretamente).
+    pub fn programar(&self, sequencias: &[&[usize]]) -> Option<Vec<Vec<TarefaAlocada>>> {
+        let mut fim: Vec<Option<f64>> = vec![None; self.n_tarefas()];
+        let mut alocacao: Vec<Vec<TarefaAlocada>> = vec![Vec::new(); sequencias.len()];
+        let total: usize = sequencias.iter().map(|s| s.len()).sum();
+        let mut programadas = 0;
+
+        while programadas < total {
+            let mut avancou = false;
+            for (maquina, sequencia) in sequencias.iter().enumerate() {
+                while let Some(&tarefa) = sequencia.get(alocacao[maquina].len()) {
+                    let predecessores = self.predecessores(tarefa);
+                    let Some(fim_predecessores) = predecessores
+                        .iter()
+                        .try_fold(0.0, |acc: f64, &p| fim[p].map(|f| acc.max(f)))
+                    else {
+                        break;
+                    };
+                    let anterior = alocacao[maquina].last();
+                    let pronta = anterior.map_or(0.0, |a| a.fim)
+                        + self.setup(anterior.map(|a| a.tarefa), tarefa);
+                    let inicio = pronta.max(self.liberacao(tarefa)).max(fim_predecessores);
+                    let termino = inicio + self.duracao(tarefa, maquina);
+                    fim[tarefa] = Some(termino);
+                    alocacao[maquina].push(TarefaAlocada {
+                        tarefa,
+                        inicio,
+                        fim: termino,
+                    });
+                    programadas += 1;
+                    avancou = true;
+                }
+            }
+            if !avancou {
+                return None;
+            }
+        }
+        Some(alocacao)
+    }
+
+    /// Predecessores diretos da tarefa (vazio sem precedências).
+    pub fn predecessores(&self, tarefa: usize) -> &[usize] {
+        self.precedencias
+            .as_ref()
+            .map_or(&[], |p| &p.predecessores[tarefa])
+    }
+
+    /// Tempo de preparação para processar `tarefa` logo após `anterior` na
+    /// mesma máquina (zero para a primeira tarefa da máquina).
+    pub fn setup(&self, anterior: Option<usize>, tarefa: usize) -> f64 {
+        match (&self.setup, anterior) {
+            (Some(setup), Some(anterior)) => {
+                setup.matriz[setup.familias[anterior]][setup.familias[tarefa]] as f64
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Aplica as restrições de elegibilidade à instância. Falha se alguma
+    /// tarefa ficar sem máquina elegível, pois então não há alocação viável.
+    pub fn restringir(mut self, elegibilidade: &Elegibilidade, semente: u64) -> io::Result<Self> {
+        let (n, m) = (self.n_tarefas(), self.n_maquinas());
+        let inviavel = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+
+        let elegiveis: Vec<Vec<usize>> = match elegibilidade {
+            Elegibilidade::Todas => return Ok(self),
+            Elegibilidade::Aleatoria(fracao) => {
+                // Fluxo separado do usado para os tempos, para não correlacioná-los
+                let mut rng = StdRng::seed_from_u64(semente ^ 0x9E37_79B9_7F4A_7C15);
+                let k = ((m as f64 * fracao).round() as usize).clamp(1, m);
+                (0..n)
+                    .map(|_| {
+                        let mut maquinas = rand::seq::index::sample(&mut rng, m, k).into_vec();
+                        maquinas.sort_unstable();
+                        maquinas
+                    })
+                    .collect()
+            }
+            Elegibilidade::Arquivo(caminho, conjuntos) => {
+                let mut elegiveis: Vec<Vec<usize>> = vec![(0..m).collect(); n];
+                for (tarefa, maquinas) in conjuntos {
+                    if *tarefa >= n {
+                        return Err(inviavel(format!(
+                            "{caminho}: tarefa {tarefa} não existe (n={n})"
+                        )));
+                    }
+                    if let Some(i) = maquinas.iter().find(|&&i| i >= m) {
+                        return Err(inviavel(format!(
+                            "{caminho}: máquina {i} da tarefa {tarefa} não existe (m={m})"
+                        )));
+                    }
+                    elegiveis[*tarefa] = maquinas.clone();
+                }
+                elegiveis
+            }
+        };
+
+        if let Some(tarefa) = elegiveis.iter().position(|e| e.is_empty()) {
+            return Err(inviavel(format!(
+                "instância inviável: a tarefa {tarefa} não tem máquina elegível"
+            )));
+        }
+        self.elegiveis = Some(elegiveis);
+        self.ambiente = format!("{}+{}", self.ambiente, elegibilidade.nome());
+        Ok(self)
+    }
+
+    /// Se a tarefa pode ser processada na máquina.
+    pub fn elegivel(&self, tarefa: usize, maquina: usize) -> bool {
+        self.elegiveis
+            .as_ref()
+            .is_none_or(|e| e[tarefa].contains(&maquina))
+    }
+
+    /// Confere se a alocação contém cada tarefa exatamente uma vez, apenas
+    /// em máquinas elegíveis, sem começar antes do fim dos predecessores e
+    /// dentro do orçamento de migrações.
+    pub fn verificar(&self, alocacao: &[Vec<TarefaAlocada>]) -> Result<(), String> {
+        let mut vistas = vec![false; self.n_tarefas()];
+        for (maquina, tarefas) in alocacao.iter().enumerate() {
+            for alocada in tarefas {
+                let tarefa = alocada.tarefa;
+                if tarefa >= vistas.len() || vistas[tarefa] {
+                    return Err(format!("tarefa {tarefa} alocada mais de uma vez"));
+                }
+                vistas[tarefa] = true;
+                if !self.elegivel(tarefa, maquina) {
+                    return Err(format!(
+                        "tarefa {tarefa} alocada na máquina {maquina}, que não é elegível"
+                    ));
+                }
+            }
+        }
+        if let Some(tarefa) = vistas.iter().position(|v| !v) {
+            return Err(format!("tarefa {tarefa} não foi alocada"));
+        }
+
+        if self.precedencias.is_some() {
+            let mut periodo = vec![(0.0, 0.0); self.n_tarefas()];
+            for alocada in alocacao.iter().flatten() {
+                periodo[alocada.tarefa] = (alocada.inicio, alocada.fim);
+            }
+            for tarefa in 0..self.n_tarefas() {
+                for &p in self.predecessores(tarefa) {
+                    if periodo[tarefa].0 < periodo[p].1 - 1e-9 {
+                        return Err(format!("tarefa {tarefa} começa antes do fim de {p}"));
+                    }
+                }
+            }
+        }
+
+        let orcamento = self.reprogramacao.as_ref().and_then(|r| r.orcamento);
+        if let (Some(k), Some(migracoes)) = (orcamento, self.migracoes(alocacao)) {
+            if migracoes > k {
+                return Err(format!("{migracoes} migrações, acima do máximo de {k}"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn n_tarefas(&self) -> usize {
+        self.tempos.len()
+    }
+
+    pub fn n_maquinas(&self) -> usize {
+        self.velocidades.len()
+    }
+
+    /// Tempo de processamento da tarefa `tarefa` na máquina `maquina`.
+    pub fn duracao(&self, tarefa: usize, maquina: usize) -> f64 {
+        match &self.matriz {
+            Some(matriz) => matriz[tarefa][maquina] as f64,
+            None => self.tempos[tarefa] as f64 / self.velocidades[maquina],
+        }
+    }
+
+    pub fn identicas(&self) -> bool {
+        self.matriz.is_none() && self.velocidades.iter().all(|s| *s == 1.0)
+    }
+
+    /// Limite inferior do makespan.
+    ///
+    /// Q||Cmax: para cada k, as k maiores tarefas não podem terminar antes de
+    /// ocuparem as k máquinas mais rápidas, e o total de trabalho não termina
+    /// antes de Σp / Σs. Com máquinas idênticas reduz-se ao clássico
+    /// max(⌈Σp / m⌉, max p).
+    ///
+    /// R||Cmax: cada tarefa leva ao menos seu menor tempo, então
+    /// max(max_j min_i p_ij, ⌈Σ_j min_i p_ij / m⌉).
+    ///
+    /// Com datas de liberação e precedências, nenhuma tarefa termina antes do
+    /// caminho crítico até ela: max(r_j, término mais cedo dos predecessores)
+    /// mais seu menor tempo de processamento.
+    pub fn limite_inferior(&self) -> f64 {
+        let ordem = match &self.precedencias {
+            Some(grafo) => grafo.ordem(),
+            None => (0..self.n_tarefas()).collect(),
+        };
+        let mut termino = vec![0.0; self.n_tarefas()];
+        for j in ordem {
+            let menor = (0..self.n_maquinas())
+                .map(|i| self.duracao(j, i))
+                .fold(f64::INFINITY, f64::min);
+            let pronta = self
+                .predecessores(j)
+                .iter()
+                .map(|&p| termino[p])
+                .fold(self.liberacao(j), f64::max);
+            termino[j] = pronta + menor;
+        }
+        let caminho = termino.into_iter().fold(0.0, f64::max);
+        self.limite_carga().max(caminho)
+    }
+
+    fn limite_carga(&self) -> f64 {
+        if self.matriz.is_some() {
+            // `tempos` guarda min_i p_ij
+            let soma: u64 = self.tempos.iter().map(|&p| p as u64).sum();
+            let maior = self.tempos.iter().copied().max().unwrap_or(0);
+            let m = self.n_maquinas().max(1) as u64;
+            return soma.div_ceil(m).max(maior as u64) as f64;
+        }
+
+        let mut tempos: Vec<f64> = self.tempos.iter().map(|&p| p as f64).collect();
+        tempos.sort_by(|a, b| b.total_cmp(a));
+        let mut velocidades = self.velocidades.clone();
+        velocidades.sort_by(|a, b| b.total_cmp(a));
+
+        let mut limite = tempos.iter().sum::<f64>() / velocidades.iter().sum::<f64>().max(1e-9);
+        let (mut soma_p, mut soma_s) = (0.0, 0.0);
+        for (p, s) in tempos.iter().zip(&velocidades) {
+            soma_p += p;
+            soma_s += s;
+            limite = limite.max(soma_p / soma_s);
+        }
+
+        if self.identicas() {
+            limite.ceil()
+        } else {
+            limite
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ArquivoTemporario;
+
+    /// Grava `conteudo` num arquivo temporário e aplica `ler` ao caminho.
+    fn com_arquivo<T>(nome: &str, conteudo: &str, ler: impl FnOnce(&str) -> T) -> T {
+        ler(&ArquivoTemporario::com(nome, conteudo).texto())
+    }
+
+    fn invalido(resultado: io::Result<Instancia>) -> bool {
+        resultado.is_err_and(|e| e.kind() == io::ErrorKind::InvalidData)
+    }
+
+    #[test]
+    fn ler_matriz_valida() {
+        let conteudo = "# comentário\n2 3\n1 2 3\n\n4 5 6\n";
+        let instancia = com_arquivo("matriz", conteudo, Instancia::ler_matriz).unwrap();
+        assert_eq!((instancia.n_tarefas(), instancia.n_maquinas()), (2, 3));
+        assert_eq!(instancia.duracao(1, 2), 6.0);
+    }
+
+    #[test]
+    fn ler_matriz_invalida() {
+        for conteudo in [
+            "0 3\n",
+            "2 0\n",
+            "2 3\n1 2 3\n4 5\n",
+            "2 3\n1 2 3\n",
+            "1 2\n1 x\n",
+            "2\n1 2\n",
+            "",
+        ] {
+            let resultado = com_arquivo("matriz-invalida", conteudo, Instancia::ler_matriz);
+            assert!(invalido(resultado), "{conteudo:?}");
+        }
+    }
+
+    #[test]
+    fn distribuicao_valida() {
+        let casos = [
+            ("uniforme:1,100", Distribuicao::PADRAO),
+            (
+                "normal:50, 10.5",
+                Distribuicao::Normal {
+                    media: 50.0,
+                    desvio: 10.5,
+                },
+            ),
+            ("exponencial:20", Distribuicao::Exponencial { media: 20.0 }),
+            (
+                "lognormal:3,0.5",
+                Distribuicao::LogNormal {
+                    mu: 3.0,
+                    sigma: 0.5,
+                },
+            ),
+            (
+                "bimodal:1,10,90,100,0.2",
+                Distribuicao::Bimodal {
+                    curtas: (1, 10),
+                    longas: (90, 100),
+                    fracao_longas: 0.2,
+                },
+            ),
+            ("classe:5", Distribuicao::Classe(5)),
+        ];
+        for (texto, esperada) in casos {
+            assert!(Distribuicao::ler(texto) == Some(esperada), "{texto}");
+        }
+    }
+
+    #[test]
+    fn distribuicao_invalida() {
+        for texto in [
+            "uniforme",
+            "uniforme:10,1",
+            "uniforme:0,10",
+            "uniforme:1.5,10",
+            "uniforme:1,10,20",
+            "normal:50,-1",
+            "exponencial:0",
+            "lognormal:3,-0.5",
+            "bimodal:1,10,90,100,1.5",
+            "bimodal:1,10,90",
+            "classe:0",
+            "classe:6",
+            "poisson:3",
+            "normal:a,b",
+        ] {
+            assert!(Distribuicao::ler(texto).is_none(), "{texto}");
+        }
+    }
+
+    #[test]
+    fn ler_benchmark_formatos() {
+        let identicas = com_arquivo(
+            "bench-p",
+            "# P||Cmax\n3 2\n4 5 6\n",
+            Instancia::ler_benchmark,
+        );
+        let identicas = identicas.unwrap();
+        assert!(identicas.identicas());
+        assert_eq!((identicas.n_tarefas(), identicas.n_maquinas()), (3, 2));
+        assert_eq!(identicas.duracao(2, 1), 6.0);
+
+        let matriz = com_arquivo("bench-r", "2 2\n1 2\n3 4\n", Instancia::ler_benchmark);
+        let matriz = matriz.unwrap();
+        assert_eq!((matriz.duracao(0, 1), matriz.duracao(1, 0)), (2.0, 3.0));
+
+        let pares = com_arquivo(
+            "bench-pares",
+            "2 2\n1 7 0 8\n0 9 1 5\n",
+            Instancia::ler_benchmark,
+        );
+        let pares = pares.unwrap();
+        assert_eq!((pares.duracao(0, 0), pares.duracao(0, 1)), (8.0, 7.0));
+        assert_eq!((pares.duracao(1, 0), pares.duracao(1, 1)), (9.0, 5.0));
+    }
+
+    #[test]
+    fn ler_benchmark_invalido() {
+        for conteudo in [
+            "",
+            "3\n",
+            "0 2\n",
+            "2 0\n",
+            "2 2\n1 2 3\n",
+            "2 2\n1 x\n",
+            "2 2\n0 1 0 2\n0 3 1 4\n",
+            "2 2\n0 1 2 2\n0 3 1 4\n",
+        ] {
+            let resultado = com_arquivo("bench-invalido", conteudo, Instancia::ler_benchmark);
+            assert!(invalido(resultado), "{conteudo:?}");
+        }
+    }
+
+    #[test]
+    fn prazos_com_parametros_degenerados_nao_entram_em_panico() {
+        let base = Instancia::gerar(12, 3, &Ambiente::padroes()[0], &Distribuicao::PADRAO, 5);
+        for (tf, rdd) in [
+            (0.2, -0.6),
+            (3.0, 0.2),
+            (-2.0, 1.0),
+            (0.5, f64::INFINITY),
+            (f64::NAN, 0.4),
+        ] {
+            let instancia = base.clone().com_prazos(&Prazos::Aleatorios { tf, rdd }, 9);
+            let datas = instancia.datas.as_ref().unwrap();
+            for (j, &tempo) in instancia.tempos.iter().enumerate() {
+                assert!(
+                    datas.entrega[j] >= datas.liberacao[j] + tempo,
+                    "tf={tf} rdd={rdd}"
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,18 @@
+mod ajuste;
+mod analise;
+mod benchmark;
 mod blm;
 mod blnm;
+mod cli;
+mod comparacao;
+mod config;
+mod estatistica;
+mod historico;
+mod instancia;
+mod objetivo;
+mod online;
+mod pareto;
+mod precedencia;
 mod ui;
 mod utils;
 
@@ -11,14 +24,38 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 
+/// Ponto de entrada de um subcomando de linha de comando, com os argumentos seguintes.
+type Subcomando = fn(&[String]) -> io::Result<()>;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    // Subcomandos em modo texto; sem eles, abre a interface
+    let subcomando: Option<Subcomando> = match args.get(1).map(String::as_str) {
+        Some("analyze") => Some(analise::executar),
+        Some("online") => Some(online::executar),
+        Some("tune") => Some(ajuste::executar),
+        _ => None,
+    };
+    if let Some(executar) = subcomando {
+        if let Err(err) = executar(&args[2..]) {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let mut app = ui::App::new();
+    if let Err(err) = cli::aplicar_opcoes(&mut app, &args[1..]) {
+        eprintln!("{err}");
+        std::process::exit(2);
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let app = ui::App::new();
     let res = ui::run_app(&mut terminal, app);
 
     disable_raw_mode()?;
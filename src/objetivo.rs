@@ -0,0 +1,366 @@
+use crate::blm::{sequencias, Maquina};
+use crate::instancia::Instancia;
+use crate::utils::TarefaAlocada;
+
+/// Critério otimizado pelas buscas. Todos são minimizados.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Objetivo {
+    /// Cmax: término da última tarefa.
+    Makespan,
+    /// Σ w_j C_j.
+    SomaPonderadaConclusao,
+    /// Lmax = max (C_j - d_j).
+    AtrasoMaximo,
+    /// Σ w_j max(0, C_j - d_j).
+    AtrasoPonderadoTotal,
+    /// Σ U_j, com U_j = 1 se C_j > d_j.
+    TarefasAtrasadas,
+    /// Σ C_j, sem pesos.
+    SomaConclusao,
+    /// Diferença entre o término da máquina mais e da menos carregada.
+    Desequilibrio,
+    /// Variância do término das máquinas.
+    VarianciaCarga,
+    /// Soma ponderada de Cmax, desequilíbrio e Σ C_j.
+    Combinacao {
+        makespan: f64,
+        desequilibrio: f64,
+        soma_conclusao: f64,
+    },
+}
+
+pub const OBJETIVOS: [Objetivo; 9] = [
+    Objetivo::Makespan,
+    Objetivo::SomaPonderadaConclusao,
+    Objetivo::AtrasoMaximo,
+    Objetivo::AtrasoPonderadoTotal,
+    Objetivo::TarefasAtrasadas,
+    Objetivo::SomaConclusao,
+    Objetivo::Desequilibrio,
+    Objetivo::VarianciaCarga,
+    Objetivo::Combinacao {
+        makespan: 1.0,
+        desequilibrio: 1.0,
+        soma_conclusao: 0.0,
+    },
+];
+
+/// Contribuição de uma máquina: o instante em que ela termina, a parcela
+/// do critério que depende das tarefas (Σ w_j C_j, Lmax, ...) e quantas
+/// tarefas dela vieram de outra máquina da escala atual.
+#[derive(Clone, Copy)]
+pub struct Custo {
+    pub carga: f64,
+    pub valor: f64,
+    pub migracoes: usize,
+}
+
+impl Objetivo {
+    /// Nome gravado nos resultados.
+    pub fn nome(&self) -> String {
+        match self {
+            Objetivo::Makespan => "makespan".to_string(),
+            Objetivo::SomaPonderadaConclusao => "soma-ponderada-conclusao".to_string(),
+            Objetivo::AtrasoMaximo => "atraso-maximo".to_string(),
+            Objetivo::AtrasoPonderadoTotal => "atraso-ponderado-total".to_string(),
+            Objetivo::TarefasAtrasadas => "tarefas-atrasadas".to_string(),
+            Objetivo::SomaConclusao => "soma-conclusao".to_string(),
+            Objetivo::Desequilibrio => "desequilibrio".to_string(),
+            Objetivo::VarianciaCarga => "variancia-carga".to_string(),
+            Objetivo::Combinacao {
+                makespan,
+                desequilibrio,
+                soma_conclusao,
+            } => format!("combinacao-cmax{makespan}-deseq{desequilibrio}-somac{soma_conclusao}"),
+        }
+    }
+
+    /// Nome curto exibido na interface.
+    pub fn rotulo(&self) -> String {
+        match self {
+            Objetivo::Makespan => "Makespan (Cmax)".to_string(),
+            Objetivo::SomaPonderadaConclusao => "Conclusão ponderada (ΣwC)".to_string(),
+            Objetivo::AtrasoMaximo => "Atraso máximo (Lmax)".to_string(),
+            Objetivo::AtrasoPonderadoTotal => "Atraso ponderado (ΣwT)".to_string(),
+            Objetivo::TarefasAtrasadas => "Tarefas atrasadas (ΣU)".to_string(),
+            Objetivo::SomaConclusao => "Soma das conclusões (ΣC)".to_string(),
+            Objetivo::Desequilibrio => "Desequilíbrio (max − min)".to_string(),
+            Objetivo::VarianciaCarga => "Variância das cargas".to_string(),
+            Objetivo::Combinacao {
+                makespan,
+                desequilibrio,
+                soma_conclusao,
+            } => format!("Combinação ({makespan}·Cmax + {desequilibrio}·Δ + {soma_conclusao}·ΣC)"),
+        }
+    }
+
+    /// Lê um objetivo pelo nome gravado nos resultados, inclusive o da
+    /// combinação ("combinacao-cmax1-deseq1-somac0"), que também pode vir como
+    /// "combinacao:a,b,c" (pesos de Cmax, desequilíbrio e Σ C_j).
+    pub fn ler(texto: &str) -> Option<Objetivo> {
+        let pesos = texto
+            .strip_prefix("combinacao:")
+            .map(|p| p.split(',').collect())
+            .or_else(|| {
+                let resto = texto.strip_prefix("combinacao-cmax")?;
+                let (makespan, resto) = resto.split_once("-deseq")?;
+                let (desequilibrio, soma_conclusao) = resto.split_once("-somac")?;
+                Some(vec![makespan, desequilibrio, soma_conclusao])
+            });
+        if let Some(pesos) = pesos {
+            let pesos: Vec<f64> = pesos
+                .into_iter()
+                .map(|p: &str| p.trim().parse().ok().filter(|p: &f64| *p >= 0.0))
+                .collect::<Option<_>>()?;
+            let [makespan, desequilibrio, soma_conclusao] = pesos[..] else {
+                return None;
+            };
+            return Some(Objetivo::Combinacao {
+                makespan,
+                desequilibrio,
+                soma_conclusao,
+            });
+        }
+        OBJETIVOS.into_iter().find(|o| o.nome() == texto)
+    }
+
+    /// Se o critério depende do término da máquina mais carregada.
+    fn usa_maior_carga(&self) -> bool {
+        match self {
+            Objetivo::Makespan | Objetivo::Desequilibrio => true,
+            Objetivo::Combinacao {
+                makespan,
+                desequilibrio,
+                ..
+            } => *makespan > 0.0 || *desequilibrio > 0.0,
+            _ => false,
+        }
+    }
+
+    /// Se o critério depende do término da máquina menos carregada.
+    fn usa_menor_carga(&self) -> bool {
+        match self {
+            Objetivo::Desequilibrio => true,
+            Objetivo::Combinacao { desequilibrio, .. } => *desequilibrio > 0.0,
+            _ => false,
+        }
+    }
+
+    /// Contribuição de uma máquina se processasse `tarefas` nessa ordem.
+    pub fn custo_sequencia(
+        &self,
+        maquina: &Maquina,
+        tarefas: impl Iterator<Item = usize>,
+    ) -> Custo {
+        let instancia = maquina.instancia;
+        self.custo_cronograma(
+            instancia,
+            maquina.indice,
+            instancia.sequenciar(maquina.indice, tarefas),
+        )
+    }
+
+    /// Contribuição de uma máquina com início e fim das tarefas já definidos.
+    pub fn custo_cronograma(
+        &self,
+        instancia: &Instancia,
+        maquina: usize,
+        cronograma: impl Iterator<Item = TarefaAlocada>,
+    ) -> Custo {
+        let mut custo = Custo {
+            carga: 0.0,
+            valor: match self {
+                Objetivo::AtrasoMaximo => f64::NEG_INFINITY,
+                _ => 0.0,
+            },
+            migracoes: 0,
+        };
+        for t in cronograma {
+            let atraso = t.fim - instancia.entrega(t.tarefa);
+            custo.carga = custo.carga.max(t.fim);
+            custo.migracoes += instancia.migrou(t.tarefa, maquina) as usize;
+            match self {
+                Objetivo::SomaPonderadaConclusao => custo.valor += instancia.peso(t.tarefa) * t.fim,
+                Objetivo::AtrasoMaximo => custo.valor = custo.valor.max(atraso),
+                Objetivo::AtrasoPonderadoTotal => {
+                    custo.valor += instancia.peso(t.tarefa) * atraso.max(0.0)
+                }
+                Objetivo::TarefasAtrasadas if atraso > 0.0 => custo.valor += 1.0,
+                Objetivo::SomaConclusao | Objetivo::Combinacao { .. } => custo.valor += t.fim,
+                _ => {}
+            }
+        }
+        if *self == Objetivo::Makespan {
+            custo.valor = custo.carga;
+        }
+        custo
+    }
+
+    /// Parcela da máquina no critério, usada para comparar alternativas que
+    /// só mudam essa máquina.
+    pub fn parcela(&self, custo: &Custo) -> f64 {
+        match self {
+            Objetivo::Desequilibrio | Objetivo::VarianciaCarga => custo.carga,
+            Objetivo::Combinacao {
+                makespan,
+                soma_conclusao,
+                ..
+            } => makespan * custo.carga + soma_conclusao * custo.valor,
+            _ => custo.valor,
+        }
+    }
+
+    /// Combina as contribuições das máquinas no valor do objetivo.
+    pub fn agregar(&self, custos: impl IntoIterator<Item = Custo>) -> f64 {
+        let custos: Vec<Custo> = custos.into_iter().collect();
+        let maior = custos.iter().map(|c| c.carga).fold(0.0, f64::max);
+        let menor = custos.iter().map(|c| c.carga).fold(f64::INFINITY, f64::min);
+        let soma: f64 = custos.iter().map(|c| c.valor).sum();
+        match self {
+            Objetivo::Makespan => maior,
+            Objetivo::AtrasoMaximo => custos
+                .iter()
+                .map(|c| c.valor)
+                .fold(f64::NEG_INFINITY, f64::max),
+            Objetivo::Desequilibrio => maior - menor,
+            Objetivo::VarianciaCarga => {
+                let n = custos.len().max(1) as f64;
+                let media = custos.iter().map(|c| c.carga).sum::<f64>() / n;
+                custos
+                    .iter()
+                    .map(|c| (c.carga - media).powi(2))
+                    .sum::<f64>()
+                    / n
+            }
+            Objetivo::Combinacao {
+                makespan,
+                desequilibrio,
+                soma_conclusao,
+            } => makespan * maior + desequilibrio * (maior - menor) + soma_conclusao * soma,
+            _ => soma,
+        }
+    }
+
+    /// Objetivo com a penalidade (ou o orçamento) de migrações da instância.
+    pub fn penalizado(
+        &self,
+        instancia: &Instancia,
+        custos: impl IntoIterator<Item = Custo>,
+    ) -> f64 {
+        let custos: Vec<Custo> = custos.into_iter().collect();
+        let migracoes = custos.iter().map(|c| c.migracoes).sum();
+        instancia.penalizar(self.agregar(custos), migracoes)
+    }
+
+    /// Valor penalizado do objetivo seguido, nos critérios de máximo (e de
+    /// mínimo), de quantas máquinas o atingem. Com várias máquinas empatadas
+    /// no extremo nenhum movimento isolado o altera; diminuir esse número
+    /// abre caminho para isso.
+    pub fn chave(
+        &self,
+        instancia: &Instancia,
+        custos: impl IntoIterator<Item = Custo>,
+    ) -> (f64, usize) {
+        let custos: Vec<Custo> = custos.into_iter().collect();
+        let total = self.penalizado(instancia, custos.iter().copied());
+        let cargas = custos.iter().map(|c| c.carga);
+
+        let mut criticas = 0;
+        if self.usa_maior_carga() {
+            criticas += contar_extremo(cargas.clone(), f64::max);
+        }
+        if self.usa_menor_carga() {
+            criticas += contar_extremo(cargas, f64::min);
+        }
+        if *self == Objetivo::AtrasoMaximo {
+            criticas += contar_extremo(custos.iter().map(|c| c.valor), f64::max);
+        }
+        (total, criticas)
+    }
+
+    /// Contribuição da máquina com as tarefas que ela tem hoje.
+    pub fn custo(&self, maquina: &Maquina) -> Custo {
+        self.custo_sequencia(maquina, maquina.ativas().iter().copied())
+    }
+
+    /// Contribuição de cada máquina. Com precedências as máquinas dependem
+    /// umas das outras e são programadas juntas; `None` se as ordens atuais
+    /// forem inviáveis.
+    pub fn custos(&self, maquinas: &[Maquina]) -> Option<Vec<Custo>> {
+        let Some(instancia) = maquinas.first().map(|m| m.instancia) else {
+            return Some(Vec::new());
+        };
+        if instancia.precedencias.is_none() {
+            return Some(maquinas.iter().map(|m| self.custo(m)).collect());
+        }
+        let alocacao = instancia.programar(&sequencias(maquinas))?;
+        Some(
+            alocacao
+                .into_iter()
+                .enumerate()
+                .map(|(i, cronograma)| self.custo_cronograma(instancia, i, cronograma.into_iter()))
+                .collect(),
+        )
+    }
+
+    /// Valor usado pelas buscas, com a penalidade de migrações.
+    pub fn avaliar(&self, maquinas: &[Maquina]) -> f64 {
+        match (maquinas.first(), self.custos(maquinas)) {
+            (Some(m), Some(custos)) => self.penalizado(m.instancia, custos),
+            (None, _) => self.agregar([]),
+            (_, None) => f64::INFINITY,
+        }
+    }
+
+    /// Valor do objetivo, sem penalidade, como gravado nos resultados.
+    pub fn valor(&self, maquinas: &[Maquina]) -> f64 {
+        self.custos(maquinas)
+            .map_or(f64::INFINITY, |custos| self.agregar(custos))
+    }
+}
+
+/// Quantos valores empatam no extremo dado por `escolher` (máximo ou mínimo).
+fn contar_extremo(
+    valores: impl Iterator<Item = f64> + Clone,
+    escolher: fn(f64, f64) -> f64,
+) -> usize {
+    let Some(alvo) = valores.clone().reduce(escolher) else {
+        return 0;
+    };
+    valores.filter(|&v| (v - alvo).abs() <= 1e-9).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nome_e_ler_fazem_ida_e_volta() {
+        let combinacao = Objetivo::Combinacao {
+            makespan: 0.5,
+            desequilibrio: 2.0,
+            soma_conclusao: 0.25,
+        };
+        for objetivo in OBJETIVOS.into_iter().chain([combinacao]) {
+            assert!(
+                Objetivo::ler(&objetivo.nome()) == Some(objetivo),
+                "{}",
+                objetivo.nome()
+            );
+        }
+    }
+
+    #[test]
+    fn ler_combinacao_por_pesos() {
+        let esperado = Objetivo::Combinacao {
+            makespan: 1.0,
+            desequilibrio: 0.5,
+            soma_conclusao: 0.0,
+        };
+        assert!(Objetivo::ler("combinacao:1, 0.5, 0") == Some(esperado));
+        assert!(Objetivo::ler("combinacao:1,0.5").is_none());
+        assert!(Objetivo::ler("combinacao:1,-1,0").is_none());
+        assert!(Objetivo::ler("combinacao-cmax1-deseqx-somac0").is_none());
+        assert!(Objetivo::ler("inexistente").is_none());
+    }
+}
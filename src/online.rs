@@ -0,0 +1,513 @@
+use crate::blnm::{busca_local_iterada, Adaptacao};
+use crate::instancia::{ler_velocidades, Instancia};
+use crate::objetivo::Objetivo;
+use crate::utils::TarefaAlocada;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::ops::ControlFlow;
+
+const USO_ONLINE: &str = "uso: task-scheduling-heuristics online [<chegadas.txt>|-] \
+[--m <m> | --velocidades <s1,s2,...>] [--politica <guloso|albers|reserva>]... \
+[--reserva <fração>] [--perturbacao <p>] [--max-iter <k>] [--semente <s>] \
+[--saida <online.csv>] [--silencioso]";
+
+/// Constante de competitividade do algoritmo de Albers (1999).
+const C_ALBERS: f64 = 1.923;
+
+/// Uma tarefa é longa, para a política de reserva, quando seu tempo passa
+/// deste múltiplo da média dos tempos já recebidos.
+const LIMIAR_LONGA: f64 = 2.0;
+
+/// Regra de despacho imediato: a tarefa vai para uma máquina assim que chega,
+/// sem conhecer as próximas, e não muda mais de lugar.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Politica {
+    /// Máquina em que a tarefa termina mais cedo (list scheduling de Graham).
+    Guloso,
+    /// Mantém parte das máquinas com carga baixa para tarefas grandes futuras.
+    Albers,
+    /// As tarefas curtas não usam uma fração das máquinas mais rápidas, que
+    /// fica livre para as longas.
+    Reserva,
+}
+
+pub const POLITICAS: [Politica; 3] = [Politica::Guloso, Politica::Albers, Politica::Reserva];
+
+impl Politica {
+    pub fn nome(self) -> &'static str {
+        match self {
+            Politica::Guloso => "guloso",
+            Politica::Albers => "albers",
+            Politica::Reserva => "reserva",
+        }
+    }
+
+    pub fn ler(texto: &str) -> Option<Self> {
+        POLITICAS.into_iter().find(|p| p.nome() == texto)
+    }
+}
+
+/// Estado de uma política durante o fluxo: quando cada máquina fica livre e
+/// as tarefas já despachadas para ela, na ordem de chegada.
+struct Despachante<'a> {
+    politica: Politica,
+    velocidades: &'a [f64],
+    livre: Vec<f64>,
+    sequencias: Vec<Vec<usize>>,
+    /// Máquinas que só recebem tarefas longas (política de reserva).
+    reservadas: Vec<bool>,
+    soma_tempos: f64,
+    recebidas: usize,
+}
+
+impl<'a> Despachante<'a> {
+    fn new(politica: Politica, velocidades: &'a [f64], fracao_reserva: f64) -> Self {
+        let m = velocidades.len();
+        let mut reservadas = vec![false; m];
+        if politica == Politica::Reserva && m > 1 {
+            let k = ((m as f64 * fracao_reserva).ceil() as usize).clamp(1, m - 1);
+            let mut por_velocidade: Vec<usize> = (0..m).collect();
+            por_velocidade.sort_by(|&a, &b| velocidades[b].total_cmp(&velocidades[a]));
+            for &i in &por_velocidade[..k] {
+                reservadas[i] = true;
+            }
+        }
+        Despachante {
+            politica,
+            velocidades,
+            livre: vec![0.0; m],
+            sequencias: vec![Vec::new(); m],
+            reservadas,
+            soma_tempos: 0.0,
+            recebidas: 0,
+        }
+    }
+
+    fn termino(&self, maquina: usize, chegada: u32, tempo: u32) -> f64 {
+        self.livre[maquina].max(chegada as f64) + tempo as f64 / self.velocidades[maquina]
+    }
+
+    /// Entre as máquinas aceitas pelo filtro, a que termina a tarefa mais cedo.
+    fn mais_cedo(&self, chegada: u32, tempo: u32, filtro: impl Fn(usize) -> bool) -> usize {
+        (0..self.livre.len())
+            .filter(|&i| filtro(i))
+            .min_by(|&a, &b| {
+                self.termino(a, chegada, tempo)
+                    .total_cmp(&self.termino(b, chegada, tempo))
+            })
+            .unwrap_or(0)
+    }
+
+    /// Escolhe a máquina da tarefa e a registra nela.
+    fn despachar(&mut self, tarefa: usize, chegada: u32, tempo: u32) -> usize {
+        let m = self.livre.len();
+        let maquina = match self.politica {
+            Politica::Guloso => self.mais_cedo(chegada, tempo, |_| true),
+            Politica::Albers if m >= 3 => {
+                // Máquinas por carga crescente: as k = ⌊0,36m⌋ primeiras formam
+                // o grupo baixo, que só recebe a tarefa se o grupo alto estiver cheio
+                let agora = chegada as f64;
+                let cargas: Vec<f64> = self.livre.iter().map(|&l| l.max(agora)).collect();
+                let mut ordem: Vec<usize> = (0..m).collect();
+                ordem.sort_by(|&a, &b| cargas[a].total_cmp(&cargas[b]));
+                let k = (0.36 * m as f64).floor() as usize;
+                let j = 0.29 * m as f64;
+                let alfa =
+                    ((C_ALBERS - 1.0) * k as f64 - j / 2.0) / ((C_ALBERS - 1.0) * (m - k) as f64);
+                let media = |grupo: &[usize]| {
+                    grupo.iter().map(|&i| cargas[i]).sum::<f64>() / grupo.len().max(1) as f64
+                };
+                let (baixo, alto) = ordem.split_at(k);
+                let total = cargas.iter().sum::<f64>() + tempo as f64;
+                let candidata = alto[0];
+                if media(baixo) - agora <= alfa * (media(alto) - agora)
+                    && self.termino(candidata, chegada, tempo)
+                        <= agora + C_ALBERS * (total / m as f64 - agora)
+                {
+                    candidata
+                } else {
+                    self.mais_cedo(chegada, tempo, |_| true)
+                }
+            }
+            // Com menos de 3 máquinas o grupo baixo ficaria vazio: vira o guloso
+            Politica::Albers => self.mais_cedo(chegada, tempo, |_| true),
+            Politica::Reserva => {
+                let longa = self.recebidas > 0
+                    && tempo as f64 > LIMIAR_LONGA * self.soma_tempos / self.recebidas as f64;
+                self.mais_cedo(chegada, tempo, |i| longa || !self.reservadas[i])
+            }
+        };
+        self.livre[maquina] = self.termino(maquina, chegada, tempo);
+        self.sequencias[maquina].push(tarefa);
+        self.soma_tempos += tempo as f64;
+        self.recebidas += 1;
+        maquina
+    }
+}
+
+/// Desempenho de uma política no fluxo, comparado ao resultado offline.
+pub struct LinhaOnline {
+    pub politica: &'static str,
+    pub makespan: f64,
+    pub makespan_offline: f64,
+    pub limite_inferior: f64,
+}
+
+impl LinhaOnline {
+    /// Razão competitiva estimada: makespan online sobre o da ILS offline.
+    pub fn razao_competitiva(&self) -> f64 {
+        self.makespan / self.makespan_offline
+    }
+
+    /// Razão sobre o limite inferior, que majora a razão em relação ao ótimo.
+    pub fn razao_limite(&self) -> f64 {
+        self.makespan / self.limite_inferior
+    }
+}
+
+/// Lê uma tarefa "chegada tempo" do fluxo.
+fn ler_chegada(linha: &str) -> Result<(u32, u32), String> {
+    let valores: Vec<u32> = linha
+        .split_whitespace()
+        .map(|v| v.parse().map_err(|_| format!("valor inválido: {v}")))
+        .collect::<Result<_, _>>()?;
+    match valores[..] {
+        [chegada, tempo] => Ok((chegada, tempo)),
+        _ => Err(format!("esperado \"chegada tempo\": {linha}")),
+    }
+}
+
+fn alocacao(instancia: &Instancia, sequencias: &[Vec<usize>]) -> Vec<Vec<TarefaAlocada>> {
+    sequencias
+        .iter()
+        .enumerate()
+        .map(|(i, tarefas)| instancia.sequenciar(i, tarefas.iter().copied()).collect())
+        .collect()
+}
+
+fn makespan(alocacao: &[Vec<TarefaAlocada>]) -> f64 {
+    alocacao.iter().flatten().map(|t| t.fim).fold(0.0, f64::max)
+}
+
+pub fn salvar_online_csv(
+    linhas: &[LinhaOnline],
+    n: usize,
+    m: usize,
+    caminho: &str,
+) -> io::Result<()> {
+    let mut file = File::create(caminho)?;
+    writeln!(
+        file,
+        "politica,n,m,makespan,makespan_offline,limite_inferior,razao_competitiva,razao_limite"
+    )?;
+    for l in linhas {
+        writeln!(
+            file,
+            "{},{},{},{:.2},{:.2},{:.2},{:.4},{:.4}",
+            l.politica,
+            n,
+            m,
+            l.makespan,
+            l.makespan_offline,
+            l.limite_inferior,
+            l.razao_competitiva(),
+            l.razao_limite()
+        )?;
+    }
+    Ok(())
+}
+
+/// Modo `online`: recebe as tarefas uma a uma, de arquivo ou da entrada
+/// padrão, e despacha cada uma imediatamente por cada política. Ao fim do
+/// fluxo, compara o makespan de cada política com o da busca local iterada
+/// sobre o mesmo conjunto de tarefas (chegadas como datas de liberação).
+pub fn executar(args: &[String]) -> io::Result<()> {
+    let invalido =
+        |msg: String| io::Error::new(io::ErrorKind::InvalidInput, format!("{msg}\n{USO_ONLINE}"));
+    let mut entrada = None;
+    let mut velocidades = vec![1.0; 2];
+    let mut politicas = Vec::new();
+    let mut fracao_reserva = 0.25;
+    let mut perturbacao = 0.1;
+    let mut max_iter = 1000;
+    let mut semente: u64 = rand::random();
+    let mut saida = None;
+    let mut silencioso = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut valor = |opcao: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| invalido(format!("{opcao} exige um valor")))
+        };
+        match arg.as_str() {
+            "--m" => {
+                let texto = valor(arg)?;
+                let m: usize = texto
+                    .parse()
+                    .ok()
+                    .filter(|&m| m > 0)
+                    .ok_or_else(|| invalido(format!("número de máquinas inválido: {texto}")))?;
+                velocidades = vec![1.0; m];
+            }
+            "--velocidades" => {
+                let texto = valor(arg)?;
+                velocidades = ler_velocidades(&texto)
+                    .ok_or_else(|| invalido(format!("velocidades inválidas: {texto}")))?;
+            }
+            "--politica" => {
+                let texto = valor(arg)?;
+                politicas.push(
+                    Politica::ler(&texto)
+                        .ok_or_else(|| invalido(format!("política desconhecida: {texto}")))?,
+                );
+            }
+            "--reserva" => {
+                let texto = valor(arg)?;
+                fracao_reserva = texto
+                    .parse()
+                    .ok()
+                    .filter(|f| (0.0..1.0).contains(f))
+                    .ok_or_else(|| invalido(format!("fração de reserva inválida: {texto}")))?;
+            }
+            "--perturbacao" => {
+                let texto = valor(arg)?;
+                perturbacao = texto
+                    .parse()
+                    .ok()
+                    .filter(|p| (0.0..=1.0).contains(p))
+                    .ok_or_else(|| invalido(format!("perturbação inválida: {texto}")))?;
+            }
+            "--max-iter" => {
+                let texto = valor(arg)?;
+                max_iter = texto
+                    .parse()
+                    .map_err(|_| invalido(format!("máximo de iterações inválido: {texto}")))?;
+            }
+            "--semente" => {
+                let texto = valor(arg)?;
+                semente = texto
+                    .parse()
+                    .map_err(|_| invalido(format!("semente inválida: {texto}")))?;
+            }
+            "--saida" | "-o" => saida = Some(valor(arg)?),
+            "--silencioso" => silencioso = true,
+            "--help" | "-h" => return Err(invalido(String::new())),
+            outro if outro.starts_with("--") => {
+                return Err(invalido(format!("opção desconhecida: {outro}")))
+            }
+            _ => entrada = Some(arg.clone()),
+        }
+    }
+    if politicas.is_empty() {
+        politicas = POLITICAS.to_vec();
+    }
+
+    let leitor: Box<dyn BufRead> = match entrada.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(caminho) => Box::new(BufReader::new(File::open(caminho)?)),
+    };
+    let origem = entrada.as_deref().unwrap_or("-").to_string();
+    let erro_fluxo = |linha: usize, msg: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{origem}:{linha}: {msg}"),
+        )
+    };
+
+    let mut despachantes: Vec<Despachante> = politicas
+        .iter()
+        .map(|&p| Despachante::new(p, &velocidades, fracao_reserva))
+        .collect();
+    let mut tempos = Vec::new();
+    let mut chegadas = Vec::new();
+    let mut stdout = io::stdout().lock();
+    for (numero, linha) in leitor.lines().enumerate() {
+        let linha = linha?;
+        let linha = linha.trim();
+        if linha.is_empty() || linha.starts_with('#') {
+            continue;
+        }
+        let (chegada, tempo) = ler_chegada(linha).map_err(|e| erro_fluxo(numero + 1, e))?;
+        if chegadas.last().is_some_and(|&anterior| chegada < anterior) {
+            return Err(erro_fluxo(
+                numero + 1,
+                format!("chegada {chegada} anterior à da tarefa precedente"),
+            ));
+        }
+
+        let tarefa = tempos.len();
+        tempos.push(tempo);
+        chegadas.push(chegada);
+        let destinos: Vec<String> = despachantes
+            .iter_mut()
+            .map(|d| {
+                format!(
+                    "{}→M{}",
+                    d.politica.nome(),
+                    d.despachar(tarefa, chegada, tempo)
+                )
+            })
+            .collect();
+        if !silencioso {
+            writeln!(
+                stdout,
+                "t={chegada} tarefa {tarefa} (p={tempo}): {}",
+                destinos.join(" ")
+            )?;
+            stdout.flush()?;
+        }
+    }
+    drop(stdout);
+    if tempos.is_empty() {
+        return Err(invalido("nenhuma tarefa recebida".to_string()));
+    }
+
+    let (n, m) = (tempos.len(), velocidades.len());
+    let instancia = Instancia::de_chegadas(tempos, chegadas, velocidades.clone());
+    let offline = busca_local_iterada(
+        &instancia,
+        Objetivo::Makespan,
+        None,
+        perturbacao,
+        Adaptacao::Fixa,
+        max_iter,
+        semente,
+        &mut |_| ControlFlow::Continue(()),
+    );
+    instancia
+        .verificar(&offline.alocacao)
+        .map_err(|e| io::Error::other(format!("alocação offline inválida: {e}")))?;
+    let limite_inferior = instancia.limite_inferior();
+
+    let mut linhas = Vec::new();
+    for despachante in &despachantes {
+        let alocacao = alocacao(&instancia, &despachante.sequencias);
+        instancia.verificar(&alocacao).map_err(|e| {
+            io::Error::other(format!(
+                "alocação inválida da política {}: {e}",
+                despachante.politica.nome()
+            ))
+        })?;
+        linhas.push(LinhaOnline {
+            politica: despachante.politica.nome(),
+            makespan: makespan(&alocacao),
+            makespan_offline: offline.valor_final,
+            limite_inferior,
+        });
+    }
+
+    println!(
+        "=== online: n={n} m={m} ambiente={} semente={semente} ===",
+        instancia.ambiente
+    );
+    println!(
+        "ILS offline (p={perturbacao}, max-iter={max_iter}): makespan {:.2} | limite inferior {:.2}",
+        offline.valor_final, limite_inferior
+    );
+    println!(
+        "{:<10} {:>12} {:>14} {:>14}",
+        "política", "makespan", "razão (ILS)", "razão (LB)"
+    );
+    for l in &linhas {
+        println!(
+            "{:<10} {:>12.2} {:>14.4} {:>14.4}",
+            l.politica,
+            l.makespan,
+            l.razao_competitiva(),
+            l.razao_limite()
+        );
+    }
+
+    if let Some(saida) = saida {
+        salvar_online_csv(&linhas, n, m, &saida)?;
+        println!("Comparação salva em: {saida}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn despachante<'a>(
+        politica: Politica,
+        velocidades: &'a [f64],
+        livre: &[f64],
+    ) -> Despachante<'a> {
+        let mut despachante = Despachante::new(politica, velocidades, 0.25);
+        despachante.livre = livre.to_vec();
+        despachante
+    }
+
+    #[test]
+    fn guloso_escolhe_o_termino_mais_cedo() {
+        let velocidades = [1.0, 2.0];
+        let mut guloso = despachante(Politica::Guloso, &velocidades, &[0.0, 0.0]);
+        assert_eq!(guloso.despachar(0, 0, 10), 1);
+        assert_eq!(guloso.livre, [0.0, 5.0]);
+        // M0 termina em 10 e M1 em 5 + 10/2 = 10,5
+        assert_eq!(guloso.despachar(1, 0, 10), 0);
+        assert_eq!(guloso.sequencias, [vec![1], vec![0]]);
+    }
+
+    #[test]
+    fn albers_com_menos_de_3_maquinas_e_o_guloso() {
+        for livre in [[5.0, 2.0], [1.0, 4.0]] {
+            let velocidades = [1.0, 1.0];
+            let esperada = despachante(Politica::Guloso, &velocidades, &livre).despachar(0, 0, 7);
+            let albers = despachante(Politica::Albers, &velocidades, &livre).despachar(0, 0, 7);
+            assert_eq!(albers, esperada, "{livre:?}");
+        }
+        assert_eq!(
+            despachante(Politica::Albers, &[1.0], &[9.0]).despachar(0, 0, 3),
+            0
+        );
+    }
+
+    #[test]
+    fn albers_preserva_o_grupo_baixo_quando_ha_folga() {
+        // m = 3: k = 1, o grupo baixo é só M0, vazia; o alto tem carga 10
+        let velocidades = [1.0; 3];
+        let livre = [0.0, 10.0, 10.0];
+        // M1 termina em 15 ≤ 1,923 · 25/3: vai para o grupo alto
+        assert_eq!(
+            despachante(Politica::Albers, &velocidades, &livre).despachar(0, 0, 5),
+            1
+        );
+        // Tarefa longa passaria do limite c · L/m: cai no guloso (M0)
+        assert_eq!(
+            despachante(Politica::Albers, &velocidades, &livre).despachar(0, 0, 30),
+            0
+        );
+        // Grupo baixo já carregado em relação ao alto: também guloso
+        let livre = [8.0, 10.0, 10.0];
+        assert_eq!(
+            despachante(Politica::Albers, &velocidades, &livre).despachar(0, 0, 5),
+            0
+        );
+    }
+
+    #[test]
+    fn reserva_guarda_as_maquinas_rapidas_para_tarefas_longas() {
+        let velocidades = [1.0, 1.0, 2.0, 3.0];
+        let mut reserva = Despachante::new(Politica::Reserva, &velocidades, 0.25);
+        assert_eq!(reserva.reservadas, [false, false, false, true]);
+        assert_eq!(reserva.despachar(0, 0, 10), 2);
+        for tarefa in 1..6 {
+            assert_ne!(reserva.despachar(tarefa, 0, 10), 3);
+        }
+        // Mais que o dobro da média: pode usar a máquina reservada
+        assert_eq!(reserva.despachar(6, 0, 30), 3);
+    }
+
+    #[test]
+    fn ler_chegada_valida_e_invalida() {
+        assert_eq!(ler_chegada("3 10"), Ok((3, 10)));
+        assert_eq!(ler_chegada("  0\t7 "), Ok((0, 7)));
+        for linha in ["", "3", "3 10 4", "a 10", "-1 10", "3 1.5"] {
+            assert!(ler_chegada(linha).is_err(), "{linha:?}");
+        }
+    }
+}
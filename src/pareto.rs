@@ -0,0 +1,355 @@
+use crate::blm::{extrair_alocacao, Maquina};
+use crate::instancia::Instancia;
+use crate::objetivo::Objetivo;
+use crate::utils::{PontoConvergencia, PontoPareto, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::ops::ControlFlow;
+use std::time::Instant;
+
+/// Nome gravado na coluna de objetivo dos resultados do modo multiobjetivo.
+pub const OBJETIVO_PARETO: &str = "pareto-makespan-variancia-migracoes";
+
+/// Solução do arquivo: máquinas, critérios (makespan, variância das cargas,
+/// migrações) e se a vizinhança já foi explorada.
+struct Candidato<'a> {
+    maquinas: Vec<Maquina<'a>>,
+    criterios: [f64; 3],
+    explorado: bool,
+}
+
+/// Se `a` domina `b`: não é pior em nenhum critério e é melhor em algum.
+fn domina(a: &[f64; 3], b: &[f64; 3]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x <= y) && a.iter().zip(b).any(|(x, y)| x < y)
+}
+
+/// Escala de referência para contar migrações: a escala atual, na
+/// reprogramação; senão cada tarefa, na ordem (ou numa ordem topológica, com
+/// precedências), vai para a máquina elegível onde termina mais cedo.
+pub fn escala_base(instancia: &Instancia) -> Vec<Maquina<'_>> {
+    let mut maquinas: Vec<Maquina> = (0..instancia.n_maquinas())
+        .map(|i| Maquina::new(instancia, i))
+        .collect();
+    if let Some(reprogramacao) = &instancia.reprogramacao {
+        for (maquina, sequencia) in maquinas.iter_mut().zip(&reprogramacao.sequencias) {
+            maquina.definir(sequencia);
+        }
+        return maquinas;
+    }
+    let ordem = match &instancia.precedencias {
+        Some(grafo) => grafo.ordem(),
+        None => (0..instancia.n_tarefas()).collect(),
+    };
+    for tarefa in ordem {
+        let destino = maquinas
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.aceita(tarefa))
+            .map(|(i, m)| (i, m.fim_com(tarefa)))
+            .fold(None, |melhor, (i, fim)| match melhor {
+                Some((_, f)) if f <= fim => melhor,
+                _ => Some((i, fim)),
+            })
+            .map_or(0, |(i, _)| i);
+        maquinas[destino].adicionar(tarefa);
+    }
+    maquinas
+}
+
+/// Máquina de cada tarefa na solução.
+fn maquina_de(maquinas: &[Maquina]) -> Vec<usize> {
+    let n = maquinas.first().map_or(0, |m| m.tarefas.len());
+    let mut maquina_de = vec![0; n];
+    for maquina in maquinas {
+        for &tarefa in maquina.ativas() {
+            maquina_de[tarefa] = maquina.indice;
+        }
+    }
+    maquina_de
+}
+
+/// Makespan e variância das cargas, ou `None` se as precedências travarem.
+fn criterios_carga(maquinas: &[Maquina]) -> Option<[f64; 2]> {
+    let custos = Objetivo::Makespan.custos(maquinas)?;
+    Some([
+        Objetivo::Makespan.agregar(custos.iter().copied()),
+        Objetivo::VarianciaCarga.agregar(custos),
+    ])
+}
+
+/// Insere a solução no arquivo se nenhum ponto dele a domina ou empata com
+/// ela, retirando os que passam a ser dominados. Só copia as máquinas quando
+/// a solução entra.
+fn inserir_no_arquivo<'a>(
+    arquivo: &mut Vec<Candidato<'a>>,
+    maquinas: &[Maquina<'a>],
+    criterios: [f64; 3],
+) {
+    if arquivo
+        .iter()
+        .any(|c| c.criterios == criterios || domina(&c.criterios, &criterios))
+    {
+        return;
+    }
+    arquivo.retain(|c| !domina(&criterios, &c.criterios));
+    arquivo.push(Candidato {
+        maquinas: maquinas.to_vec(),
+        criterios,
+        explorado: false,
+    });
+}
+
+/// Valores de (makespan, variância, migrações) que o hipervolume leva a 0 e a 1
+/// em cada eixo. Dependem só da instância, então frentes de execuções e
+/// configurações diferentes sobre a mesma instância são comparáveis.
+#[derive(Clone, Copy)]
+pub struct LimitesHipervolume {
+    pub ideal: [f64; 3],
+    pub nadir: [f64; 3],
+}
+
+/// Ideal: o limite inferior do makespan, variância nula e nenhuma migração.
+/// Nadir: makespan e variância da escala de referência ([`escala_base`], ponto
+/// de partida da busca) e todas as tarefas migradas.
+pub fn limites_hipervolume(instancia: &Instancia) -> LimitesHipervolume {
+    let [makespan, variancia] =
+        criterios_carga(&escala_base(instancia)).unwrap_or([f64::INFINITY; 2]);
+    LimitesHipervolume {
+        ideal: [instancia.limite_inferior(), 0.0, 0.0],
+        nadir: [makespan, variancia, instancia.n_tarefas() as f64],
+    }
+}
+
+/// Hipervolume da frente com cada critério normalizado pelos `limites` (ideal
+/// em 0, nadir em 1) e ponto de referência 1,1 em todos os eixos; pontos além
+/// da referência em algum eixo não contam. O máximo, 1,331, só seria atingido
+/// por uma solução no ideal.
+pub fn hipervolume(frente: &[PontoPareto], limites: &LimitesHipervolume) -> f64 {
+    const REFERENCIA: f64 = 1.1;
+    let normalizar = |valor: f64, k: usize| {
+        let (ideal, nadir) = (limites.ideal[k], limites.nadir[k]);
+        if nadir > ideal {
+            ((valor - ideal) / (nadir - ideal)).max(0.0)
+        } else if valor <= ideal {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    };
+    let normalizados: Vec<[f64; 3]> = frente
+        .iter()
+        .map(|p| {
+            [
+                normalizar(p.makespan, 0),
+                normalizar(p.variancia, 1),
+                normalizar(p.migracoes as f64, 2),
+            ]
+        })
+        .filter(|p| p.iter().all(|&v| v < REFERENCIA))
+        .collect();
+
+    // Fatias ao longo do terceiro critério; em cada uma, a área dominada no
+    // plano dos dois primeiros pelos pontos que já entraram
+    let mut ordem = normalizados;
+    ordem.sort_by(|a, b| a[2].total_cmp(&b[2]));
+    let mut volume = 0.0;
+    for (k, ponto) in ordem.iter().enumerate() {
+        let proximo = ordem.get(k + 1).map_or(REFERENCIA, |p| p[2]);
+        if proximo > ponto[2] {
+            volume += area_dominada(&ordem[..=k], REFERENCIA) * (proximo - ponto[2]);
+        }
+    }
+    volume
+}
+
+/// Área dominada pelos pontos no plano dos dois primeiros critérios.
+fn area_dominada(pontos: &[[f64; 3]], referencia: f64) -> f64 {
+    let mut ordem: Vec<(f64, f64)> = pontos.iter().map(|p| (p[0], p[1])).collect();
+    ordem.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut area = 0.0;
+    let mut menor_y = referencia;
+    for (k, &(x, y)) in ordem.iter().enumerate() {
+        menor_y = menor_y.min(y);
+        let proximo = ordem.get(k + 1).map_or(referencia, |p| p.0);
+        area += (proximo - x) * (referencia - menor_y);
+    }
+    area
+}
+
+/// Busca local de Pareto: parte da escala de referência e, enquanto houver
+/// soluções não exploradas no arquivo (até `max_exploracoes`), sorteia uma e
+/// tenta mover cada tarefa para o fim de cada outra máquina elegível,
+/// guardando os vizinhos não dominados. Minimiza makespan, variância das
+/// cargas e migrações em relação à escala de referência, sem passar do
+/// orçamento de migrações da reprogramação.
+pub fn busca_pareto(
+    instancia: &Instancia,
+    tam_r: Option<f64>,
+    max_exploracoes: u32,
+    semente: u64,
+    observador: &mut dyn FnMut(&PontoConvergencia) -> ControlFlow<()>,
+) -> Result {
+    let mut rng = StdRng::seed_from_u64(semente);
+    let base = escala_base(instancia);
+    let maquina_base = maquina_de(&base);
+    let orcamento = instancia
+        .reprogramacao
+        .as_ref()
+        .and_then(|r| r.orcamento)
+        .map_or(f64::INFINITY, |k| k as f64);
+    let [makespan_base, variancia_base] = criterios_carga(&base).unwrap_or([f64::INFINITY; 2]);
+    let tempo_s = Instant::now();
+
+    let mut arquivo = vec![Candidato {
+        maquinas: base,
+        criterios: [makespan_base, variancia_base, 0.0],
+        explorado: false,
+    }];
+    let mut exploracoes = 0;
+    let mut convergencia = Vec::new();
+    let mut interrompida = false;
+
+    while exploracoes < max_exploracoes as usize {
+        let pendentes: Vec<usize> = (0..arquivo.len())
+            .filter(|&k| !arquivo[k].explorado)
+            .collect();
+        if pendentes.is_empty() {
+            break;
+        }
+        let escolhido = pendentes[rng.gen_range(0..pendentes.len())];
+        arquivo[escolhido].explorado = true;
+        let mut maquinas = arquivo[escolhido].maquinas.clone();
+        let criterios = arquivo[escolhido].criterios;
+        exploracoes += 1;
+
+        for a in 0..maquinas.len() {
+            for i in 0..maquinas[a].ativas().len() {
+                let tarefa = maquinas[a].tarefas[i];
+                for b in 0..maquinas.len() {
+                    if b == a || !maquinas[b].aceita(tarefa) {
+                        continue;
+                    }
+                    let migracoes = criterios[2] + (b != maquina_base[tarefa]) as u8 as f64
+                        - (a != maquina_base[tarefa]) as u8 as f64;
+                    if migracoes > orcamento {
+                        continue;
+                    }
+
+                    maquinas[a].remover(i);
+                    maquinas[b].adicionar(tarefa);
+                    if let Some([makespan, variancia]) = criterios_carga(&maquinas) {
+                        inserir_no_arquivo(
+                            &mut arquivo,
+                            &maquinas,
+                            [makespan, variancia, migracoes],
+                        );
+                    }
+                    let ultima = maquinas[b].ativas().len() - 1;
+                    maquinas[b].remover(ultima);
+                    maquinas[a].inserir(i, tarefa);
+                }
+            }
+        }
+
+        let melhor = arquivo
+            .iter()
+            .map(|c| c.criterios[0])
+            .fold(f64::INFINITY, f64::min);
+        let ponto = PontoConvergencia {
+            iteracao: exploracoes,
+            tempo_ms: tempo_s.elapsed().as_secs_f64() * 1000.0,
+            valor_atual: criterios[0],
+            valor_melhor: melhor,
+            perturbacao: 0.0,
+        };
+        let fluxo = observador(&ponto);
+        convergencia.push(ponto);
+        if fluxo.is_break() {
+            interrompida = true;
+            break;
+        }
+    }
+
+    let criterio_parada = if interrompida {
+        "interrompida"
+    } else if arquivo.iter().all(|c| c.explorado) {
+        "arquivo-explorado"
+    } else {
+        "max-exploracoes"
+    };
+    arquivo.sort_by(|a, b| a.criterios[0].total_cmp(&b.criterios[0]));
+    let frente: Vec<PontoPareto> = arquivo
+        .iter()
+        .map(|c| PontoPareto {
+            makespan: c.criterios[0],
+            variancia: c.criterios[1],
+            migracoes: c.criterios[2] as usize,
+            alocacao: extrair_alocacao(&c.maquinas),
+        })
+        .collect();
+    let tempo_exec = tempo_s.elapsed().as_secs_f64() * 1000.0;
+    let hipervolume = hipervolume(&frente, &limites_hipervolume(instancia));
+
+    Result {
+        n_tarefas: instancia.n_tarefas(),
+        n_maquinas: instancia.n_maquinas(),
+        replicacao: tam_r,
+        tempo_exec,
+        iteracoes: exploracoes,
+        valor_inicial: makespan_base,
+        valor_final: frente[0].makespan,
+        objetivo: OBJETIVO_PARETO.to_string(),
+        algoritmo: "busca-local-pareto".to_string(),
+        perturbacao: 0.0,
+        max_iteracoes: max_exploracoes,
+        criterio_parada: criterio_parada.to_string(),
+        semente,
+        ambiente: instancia.ambiente.clone(),
+        limite_inferior: instancia.limite_inferior(),
+        referencia: instancia.referencia.clone(),
+        migracoes: Some(frente[0].migracoes),
+        alocacao: frente[0].alocacao.clone(),
+        convergencia,
+        frente,
+        hipervolume: Some(hipervolume),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ponto(makespan: f64, variancia: f64, migracoes: usize) -> PontoPareto {
+        PontoPareto {
+            makespan,
+            variancia,
+            migracoes,
+            alocacao: Vec::new(),
+        }
+    }
+
+    const LIMITES: LimitesHipervolume = LimitesHipervolume {
+        ideal: [100.0, 0.0, 0.0],
+        nadir: [200.0, 10.0, 10.0],
+    };
+
+    #[test]
+    fn hipervolume_de_frente_conhecida() {
+        // Normalizados: (0.5, 0.5, 0.5) → caixa de 0.6³
+        let um = [ponto(150.0, 5.0, 5)];
+        assert!((hipervolume(&um, &LIMITES) - 0.216).abs() < 1e-12);
+
+        // (0.1, 0.6, 0) e (0.6, 0.1, 0): 2 × 0.55 menos a interseção 0.275
+        let dois = [ponto(110.0, 6.0, 0), ponto(160.0, 1.0, 0)];
+        assert!((hipervolume(&dois, &LIMITES) - 0.825).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hipervolume_usa_limites_fixos() {
+        // Um ponto só não ganha o máximo: o valor depende da distância ao ideal
+        assert!((hipervolume(&[ponto(200.0, 10.0, 10)], &LIMITES) - 0.001).abs() < 1e-12);
+        assert!((hipervolume(&[ponto(100.0, 0.0, 0)], &LIMITES) - 1.331).abs() < 1e-12);
+        // Além da referência em algum eixo, o ponto não conta
+        assert_eq!(hipervolume(&[ponto(250.0, 1.0, 0)], &LIMITES), 0.0);
+        assert_eq!(hipervolume(&[], &LIMITES), 0.0);
+    }
+}
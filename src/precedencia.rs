@@ -0,0 +1,200 @@
+use rand::Rng;
+use std::io;
+
+/// Grafo de precedências entre tarefas (DAG): `predecessores[j]` lista as
+/// tarefas que precisam terminar antes de j começar.
+#[derive(Clone)]
+pub struct Precedencias {
+    pub predecessores: Vec<Vec<usize>>,
+    pub sucessores: Vec<Vec<usize>>,
+    /// Posição de cada tarefa numa ordem topológica fixa (menor índice primeiro).
+    pub posto: Vec<usize>,
+}
+
+impl Precedencias {
+    /// Monta o grafo para n tarefas. Falha se algum arco citar tarefa
+    /// inexistente ou se houver ciclo.
+    pub fn new(n: usize, arcos: &[(usize, usize)]) -> Result<Self, String> {
+        if let Some(&(a, b)) = arcos.iter().find(|&&(a, b)| a >= n || b >= n) {
+            return Err(format!(
+                "precedência {a} → {b} cita tarefa inexistente (n={n})"
+            ));
+        }
+        if let Some(ciclo) = encontrar_ciclo(n, arcos) {
+            return Err(descrever_ciclo(&ciclo));
+        }
+
+        let mut predecessores = vec![Vec::new(); n];
+        let mut sucessores = vec![Vec::new(); n];
+        for &(a, b) in arcos {
+            if !predecessores[b].contains(&a) {
+                predecessores[b].push(a);
+                sucessores[a].push(b);
+            }
+        }
+        let mut grafo = Precedencias {
+            predecessores,
+            sucessores,
+            posto: Vec::new(),
+        };
+        grafo.posto = grafo.ordenar(|disponiveis| {
+            (0..disponiveis.len())
+                .min_by_key(|&k| disponiveis[k])
+                .unwrap_or(0)
+        });
+        Ok(grafo)
+    }
+
+    /// Ordem topológica com desempates aleatórios, como posto de cada tarefa.
+    pub fn posto_aleatorio(&self, rng: &mut impl Rng) -> Vec<usize> {
+        self.ordenar(|disponiveis| rng.gen_range(0..disponiveis.len()))
+    }
+
+    /// Ordem topológica das tarefas.
+    pub fn ordem(&self) -> Vec<usize> {
+        let mut ordem: Vec<usize> = (0..self.posto.len()).collect();
+        ordem.sort_by_key(|&t| self.posto[t]);
+        ordem
+    }
+
+    /// Algoritmo de Kahn; `escolher` decide qual das tarefas disponíveis sai
+    /// primeiro. Devolve o posto de cada tarefa.
+    fn ordenar(&self, mut escolher: impl FnMut(&[usize]) -> usize) -> Vec<usize> {
+        let n = self.predecessores.len();
+        let mut pendentes: Vec<usize> = self.predecessores.iter().map(Vec::len).collect();
+        let mut disponiveis: Vec<usize> = (0..n).filter(|&t| pendentes[t] == 0).collect();
+        let mut posto = vec![0; n];
+        let mut proximo = 0;
+        while !disponiveis.is_empty() {
+            let tarefa = disponiveis.swap_remove(escolher(&disponiveis));
+            posto[tarefa] = proximo;
+            proximo += 1;
+            for &s in &self.sucessores[tarefa] {
+                pendentes[s] -= 1;
+                if pendentes[s] == 0 {
+                    disponiveis.push(s);
+                }
+            }
+        }
+        posto
+    }
+}
+
+/// Devolve as tarefas de um ciclo (a primeira repetida no fim), se houver.
+pub fn encontrar_ciclo(n: usize, arcos: &[(usize, usize)]) -> Option<Vec<usize>> {
+    let mut sucessores = vec![Vec::new(); n];
+    for &(a, b) in arcos {
+        sucessores[a].push(b);
+    }
+
+    // Busca em profundidade iterativa: 0 = não visitada, 1 = na pilha, 2 = concluída
+    let mut estado = vec![0u8; n];
+    let mut pai = vec![usize::MAX; n];
+    for raiz in 0..n {
+        if estado[raiz] != 0 {
+            continue;
+        }
+        let mut pilha = vec![(raiz, 0)];
+        estado[raiz] = 1;
+        while let Some(&mut (tarefa, ref mut proximo)) = pilha.last_mut() {
+            if let Some(&s) = sucessores[tarefa].get(*proximo) {
+                *proximo += 1;
+                match estado[s] {
+                    0 => {
+                        estado[s] = 1;
+                        pai[s] = tarefa;
+                        pilha.push((s, 0));
+                    }
+                    1 => {
+                        let mut ciclo = vec![s];
+                        let mut atual = tarefa;
+                        while atual != s {
+                            ciclo.push(atual);
+                            atual = pai[atual];
+                        }
+                        ciclo.push(s);
+                        ciclo.reverse();
+                        return Some(ciclo);
+                    }
+                    _ => {}
+                }
+            } else {
+                estado[tarefa] = 2;
+                pilha.pop();
+            }
+        }
+    }
+    None
+}
+
+fn descrever_ciclo(ciclo: &[usize]) -> String {
+    let tarefas: Vec<String> = ciclo.iter().map(|t| t.to_string()).collect();
+    format!("ciclo nas precedências: {}", tarefas.join(" → "))
+}
+
+/// Lê arcos "a b" (a tarefa a precede a b), um por linha. Linhas iniciadas
+/// por '#' são ignoradas. Ciclos são recusados já na leitura.
+pub fn ler_arcos(caminho: &str) -> io::Result<Vec<(usize, usize)>> {
+    let conteudo = std::fs::read_to_string(caminho)?;
+    let invalido =
+        |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{caminho}: {msg}"));
+
+    let arcos: Vec<(usize, usize)> = conteudo
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|linha| {
+            let tarefas: Vec<usize> = linha
+                .split_whitespace()
+                .map(|v| {
+                    v.parse()
+                        .map_err(|_| invalido(format!("tarefa inválida: {v}")))
+                })
+                .collect::<io::Result<_>>()?;
+            match tarefas[..] {
+                [a, b] => Ok((a, b)),
+                _ => Err(invalido(format!("esperado \"a b\": {linha}"))),
+            }
+        })
+        .collect::<io::Result<_>>()?;
+
+    let n = arcos.iter().map(|&(a, b)| a.max(b) + 1).max().unwrap_or(0);
+    if let Some(ciclo) = encontrar_ciclo(n, &arcos) {
+        return Err(invalido(descrever_ciclo(&ciclo)));
+    }
+    Ok(arcos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sem_ciclo() {
+        assert_eq!(encontrar_ciclo(0, &[]), None);
+        assert_eq!(encontrar_ciclo(4, &[(0, 1), (0, 2), (1, 3), (2, 3)]), None);
+    }
+
+    #[test]
+    fn ciclo_fecha_na_primeira_tarefa() {
+        let ciclo = encontrar_ciclo(5, &[(3, 4), (0, 1), (1, 2), (2, 0)]);
+        assert_eq!(ciclo, Some(vec![0, 1, 2, 0]));
+        assert_eq!(encontrar_ciclo(3, &[(0, 1), (2, 2)]), Some(vec![2, 2]));
+        // O ciclo só é alcançado a partir de uma tarefa que não faz parte dele
+        assert_eq!(
+            encontrar_ciclo(4, &[(0, 1), (1, 2), (2, 3), (3, 1)]),
+            Some(vec![1, 2, 3, 1])
+        );
+    }
+
+    #[test]
+    fn grafo_recusa_ciclos_e_tarefas_inexistentes() {
+        let erro = Precedencias::new(3, &[(0, 1), (1, 0)]).err().unwrap();
+        assert_eq!(erro, "ciclo nas precedências: 0 → 1 → 0");
+        assert!(Precedencias::new(2, &[(0, 2)]).is_err());
+
+        let grafo = Precedencias::new(3, &[(2, 0), (2, 0), (0, 1)]).unwrap();
+        assert_eq!(grafo.predecessores[0], [2]);
+        assert_eq!(grafo.ordem(), [2, 0, 1]);
+    }
+}
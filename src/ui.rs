@@ -1,22 +1,98 @@
-use crate::blm::melhor_melhora;
-use crate::blnm::busca_local_iterada;
-use crate::utils::{salvar_csv, Result};
+use crate::blnm::Adaptacao;
+use crate::comparacao::{Comparacao, Configuracao};
+use crate::config;
+use crate::estatistica::{
+    agrupar_por_configuracao, desvio_melhor_conhecido, gap_limite_inferior, melhoria_relativa,
+    resumir, Resumo,
+};
+use crate::historico::{self, Historico};
+use crate::instancia::{
+    Ambiente, Distribuicao, Elegibilidade, Instancia, Prazos, Reprogramacao, Setup,
+};
+use crate::objetivo::{Objetivo, OBJETIVOS};
+use crate::utils::{
+    formatar_valor, juntar, ler_lista, salvar_convergencia_csv, salvar_frente_csv,
+    salvar_resultado, ConfigSaida, FormatoCsv, PontoConvergencia, Result,
+};
 use crossterm::event::{self, Event, KeyCode};
 use ratatui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Dataset, GraphType, List, ListItem, ListState,
+        Paragraph, Row, Table, TableState,
+    },
     Terminal,
 };
 use std::io;
-use std::time::Duration;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub enum Screen {
     Menu,
     Running,
     Results,
+    Gantt,
+    Frente,
+    Historico,
+    HistoricoDetalhe,
+    HistoricoComparacao,
+    Comparacao,
+}
+
+#[derive(Clone, Copy)]
+pub enum CampoTexto {
+    Diretorio,
+    Arquivo,
+    Maquinas,
+    Replicacoes,
+    Tarefas,
+    Perturbacoes,
+    Adaptacao,
+    MaxIteracoes,
+    Execucoes,
+    FiltroHistorico,
+}
+
+impl CampoTexto {
+    /// Campos gravados no arquivo de configuração, na ordem em que são escritos.
+    pub const TODOS: [CampoTexto; 9] = [
+        CampoTexto::Maquinas,
+        CampoTexto::Replicacoes,
+        CampoTexto::Tarefas,
+        CampoTexto::Perturbacoes,
+        CampoTexto::Adaptacao,
+        CampoTexto::MaxIteracoes,
+        CampoTexto::Execucoes,
+        CampoTexto::Diretorio,
+        CampoTexto::Arquivo,
+    ];
+
+    /// Nome do campo no arquivo de configuração.
+    pub fn chave(&self) -> &'static str {
+        match self {
+            CampoTexto::Diretorio => "diretorio",
+            CampoTexto::Arquivo => "arquivo",
+            CampoTexto::Maquinas => "maquinas",
+            CampoTexto::Replicacoes => "replicacao",
+            CampoTexto::Tarefas => "tarefas",
+            CampoTexto::Perturbacoes => "perturbacao",
+            CampoTexto::Adaptacao => "adaptacao",
+            CampoTexto::MaxIteracoes => "max_iter",
+            CampoTexto::Execucoes => "execucoes",
+            CampoTexto::FiltroHistorico => "filtro",
+        }
+    }
+}
+
+pub struct Edicao {
+    pub campo: CampoTexto,
+    pub buffer: String,
+    /// Motivo da recusa do último valor confirmado.
+    pub erro: Option<String>,
 }
 
 pub struct App {
@@ -28,25 +104,225 @@ pub struct App {
     pub selected_max_iter: usize,
     pub m_values: Vec<usize>,
     pub r_values: Vec<f64>,
+    /// Números de tarefas informados diretamente; vazio, n = m^r.
+    pub n_values: Vec<usize>,
+    pub selected_n: usize,
     pub perturbacao_values: Vec<f64>,
+    /// Como a ILS varia a perturbação a partir do valor selecionado (Y alterna).
+    pub adaptacao: Adaptacao,
     pub max_iter_values: Vec<u32>,
+    /// Execuções (replicações com sementes distintas) de cada configuração.
+    pub execucoes: usize,
+    pub ambientes: Vec<Ambiente>,
+    pub selected_ambiente: usize,
+    pub elegibilidades: Vec<Elegibilidade>,
+    pub selected_elegibilidade: usize,
+    pub setups: Vec<Setup>,
+    pub selected_setup: usize,
+    pub prazos: Vec<Prazos>,
+    pub selected_prazos: usize,
+    pub distribuicoes: Vec<Distribuicao>,
+    pub selected_distribuicao: usize,
+    pub objetivos: Vec<Objetivo>,
+    pub selected_objetivo: usize,
+    /// Instâncias lidas de arquivo (--matriz, --benchmark); quando houver,
+    /// a execução usa a selecionada e n e m vêm dela.
+    pub instancias_arquivo: Vec<(String, Instancia)>,
+    pub selected_instancia_arquivo: usize,
+    /// Arcos de precedência lidos de arquivo (--precedencias), aplicados a toda instância.
+    pub precedencias: Option<(String, Vec<(usize, usize)>)>,
+    /// Escala atual a reprogramar (--escala), com orçamento e penalidade de migração.
+    pub reprogramacao: Option<Reprogramacao>,
     pub results: Vec<Result>,
     pub current_exec: usize,
-    pub output_filename: String,
+    /// Tempo somado das execuções já concluídas na rodada, base da estimativa do restante.
+    pub tempo_execucoes: Duration,
+    pub saida: ConfigSaida,
+    pub edicao: Option<Edicao>,
+    /// Última falha ao gravar uma saída da rodada, com o arquivo afetado.
+    pub erro_salvar: Option<String>,
+    /// Arquivo em que as edições do menu são persistidas.
+    pub arquivo_config: String,
+    /// Texto de cada campo de `CampoTexto::TODOS` como está no arquivo de
+    /// configuração (ou o padrão), sem as opções de linha de comando.
+    pub valores_config: Vec<String>,
+    /// Falha ao ler ou gravar o arquivo de configuração.
+    pub erro_config: Option<String>,
+    /// Motivo da interrupção da execução (instância inviável ou alocação inválida).
+    pub erro_execucao: Option<String>,
+    pub salvar_convergencia: bool,
+    pub convergencia_filename: String,
+    pub convergencia_atual: Vec<PontoConvergencia>,
     pub perturbacao_state: ListState,
     pub max_iter_state: ListState,
     pub scroll_position: u16,
+    pub gantt_replicacao: usize,
+    /// Ponto da frente de Pareto exibido no Gantt; `None` mostra a alocação principal.
+    pub gantt_ponto: Option<usize>,
+    pub frente_ponto: usize,
+    pub frente_filename: String,
+    /// Configurações adicionadas (+) para serem executadas lado a lado (=).
+    pub configuracoes_comparadas: Vec<Configuracao>,
+    /// Comparação em andamento ou a última concluída.
+    pub comparacao: Option<Comparacao>,
+    /// Configuração da comparação em execução, para a tela de progresso.
+    pub configuracao_atual: usize,
+    /// Resultados de sessões anteriores, lidos ao abrir o histórico (H).
+    pub historico: Option<Historico>,
+    /// CSVs ou diretórios lidos no histórico além do diretório de saída (--historico).
+    pub historico_fontes: Vec<String>,
+    pub erro_historico: Option<String>,
     pub should_quit: bool,
 }
 
 impl App {
+    /// Número de tarefas da próxima execução e o expoente r que o gerou
+    /// (`None` quando n foi informado diretamente).
+    pub fn tamanho(&self) -> (usize, Option<f64>) {
+        let m = self.m_values[self.selected_m];
+        match self.n_values.get(self.selected_n) {
+            Some(&n) => (n, None),
+            None => {
+                let r = self.r_values[self.selected_r];
+                ((m as f64).powf(r) as usize, Some(r))
+            }
+        }
+    }
+
+    /// Valor atual do campo no formato aceito pela edição e pelo arquivo de configuração.
+    pub fn texto_campo(&self, campo: CampoTexto) -> String {
+        match campo {
+            CampoTexto::Diretorio => self.saida.diretorio.clone(),
+            CampoTexto::Arquivo => self.saida.arquivo.clone(),
+            CampoTexto::Maquinas => juntar(&self.m_values),
+            CampoTexto::Replicacoes => juntar(&self.r_values),
+            CampoTexto::Tarefas => juntar(&self.n_values),
+            CampoTexto::Perturbacoes => juntar(&self.perturbacao_values),
+            CampoTexto::Adaptacao => self.adaptacao.nome().to_string(),
+            CampoTexto::MaxIteracoes => juntar(&self.max_iter_values),
+            CampoTexto::Execucoes => self.execucoes.to_string(),
+            CampoTexto::FiltroHistorico => self
+                .historico
+                .as_ref()
+                .map_or(String::new(), |h| h.filtro.clone()),
+        }
+    }
+
+    /// Aplica o texto ao campo, voltando a seleção da lista ao primeiro valor.
+    /// Texto vazio mantém o valor atual, exceto em tarefas, onde volta a n = m^r.
+    pub fn definir_campo(
+        &mut self,
+        campo: CampoTexto,
+        texto: &str,
+    ) -> std::result::Result<(), String> {
+        let texto = texto.trim();
+        let inteiros = |minimo: f64| {
+            ler_lista(texto)
+                .filter(|v| v.iter().all(|&x| x >= minimo && x.fract() == 0.0))
+                .ok_or_else(|| format!("esperados inteiros ≥ {minimo}"))
+        };
+        match campo {
+            CampoTexto::FiltroHistorico => {
+                if let Some(historico) = self.historico.as_mut() {
+                    historico.filtrar(texto)?;
+                }
+            }
+            CampoTexto::Tarefas if texto.is_empty() => self.n_values.clear(),
+            _ if texto.is_empty() => {}
+            CampoTexto::Diretorio => self.saida.diretorio = texto.to_string(),
+            CampoTexto::Arquivo => self.saida.arquivo = texto.to_string(),
+            CampoTexto::Maquinas => {
+                self.m_values = inteiros(1.0)?.into_iter().map(|m| m as usize).collect();
+                self.selected_m = 0;
+            }
+            CampoTexto::Replicacoes => {
+                self.r_values = ler_lista(texto)
+                    .filter(|v| v.iter().all(|&r| r > 0.0))
+                    .ok_or("esperados números > 0")?;
+                self.selected_r = 0;
+            }
+            CampoTexto::Tarefas => {
+                self.n_values = inteiros(1.0)?.into_iter().map(|n| n as usize).collect();
+                self.selected_n = 0;
+            }
+            CampoTexto::Perturbacoes => {
+                self.perturbacao_values = ler_lista(texto)
+                    .filter(|v| v.iter().all(|&p| p > 0.0 && p <= 1.0))
+                    .ok_or("esperadas frações em (0, 1]")?;
+                self.selected_perturbacao = 0;
+                self.perturbacao_state.select(Some(0));
+            }
+            CampoTexto::Adaptacao => {
+                self.adaptacao = Adaptacao::ler(texto).ok_or_else(|| {
+                    let nomes: Vec<&str> = Adaptacao::TODAS.iter().map(|a| a.nome()).collect();
+                    format!("esperado um de: {}", nomes.join(", "))
+                })?;
+            }
+            CampoTexto::MaxIteracoes => {
+                self.max_iter_values = inteiros(0.0)?
+                    .into_iter()
+                    .map(|v| v.min(u32::MAX as f64) as u32)
+                    .collect();
+                self.selected_max_iter = 0;
+                self.max_iter_state.select(Some(0));
+            }
+            CampoTexto::Execucoes => {
+                self.execucoes = texto
+                    .parse()
+                    .ok()
+                    .filter(|&k| k >= 1)
+                    .ok_or("esperado um inteiro ≥ 1")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Tempo estimado até o fim da rodada pela média das execuções concluídas.
+    pub fn restante_estimado(&self) -> Option<Duration> {
+        let concluidas = self.current_exec as u32;
+        let faltam = self.execucoes.saturating_sub(self.current_exec) as u32;
+        (concluidas > 0).then(|| self.tempo_execucoes / concluidas * faltam)
+    }
+
+    pub fn instancia_arquivo(&self) -> Option<&(String, Instancia)> {
+        self.instancias_arquivo.get(self.selected_instancia_arquivo)
+    }
+
+    /// Algoritmo e parâmetros selecionados no menu.
+    pub fn configuracao(&self) -> Configuracao {
+        Configuracao {
+            algoritmo: self.selected_algorithm,
+            perturbacao: self.perturbacao_values[self.selected_perturbacao],
+            adaptacao: self.adaptacao,
+            max_iter: self.max_iter_values[self.selected_max_iter],
+        }
+    }
+
+    pub fn objetivo(&self) -> Objetivo {
+        self.objetivos[self.selected_objetivo]
+    }
+
+    /// Se o algoritmo selecionado é a busca multiobjetivo, que ignora o objetivo (C).
+    pub fn pareto(&self) -> bool {
+        self.selected_algorithm == 2
+    }
+
+    /// Rótulo do critério acompanhado na tela de progresso.
+    pub fn rotulo_objetivo(&self) -> String {
+        if self.pareto() {
+            "Pareto: makespan × variância × migrações".to_string()
+        } else {
+            self.objetivo().rotulo()
+        }
+    }
+
     pub fn new() -> Self {
         let mut perturbacao_state = ListState::default();
         perturbacao_state.select(Some(2));
         let mut max_iter_state = ListState::default();
         max_iter_state.select(Some(3));
 
-        App {
+        let mut app = App {
             current_screen: Screen::Menu,
             selected_algorithm: 0,
             selected_m: 0,
@@ -55,16 +331,89 @@ impl App {
             selected_max_iter: 3,
             m_values: vec![10, 20, 50],
             r_values: vec![1.5, 2.0],
+            n_values: Vec::new(),
+            selected_n: 0,
             perturbacao_values: vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9],
+            adaptacao: Adaptacao::Fixa,
             max_iter_values: vec![0, 100, 500, 1000, 2000, 5000],
+            execucoes: 10,
+            ambientes: Ambiente::padroes(),
+            selected_ambiente: 0,
+            elegibilidades: vec![
+                Elegibilidade::Todas,
+                Elegibilidade::Aleatoria(0.5),
+                Elegibilidade::Aleatoria(0.25),
+            ],
+            selected_elegibilidade: 0,
+            setups: vec![Setup::Nenhum, Setup::Aleatorio(3), Setup::Aleatorio(10)],
+            selected_setup: 0,
+            prazos: vec![
+                Prazos::Nenhum,
+                Prazos::Aleatorios { tf: 0.2, rdd: 0.6 },
+                Prazos::Aleatorios { tf: 0.6, rdd: 0.2 },
+            ],
+            selected_prazos: 0,
+            distribuicoes: vec![
+                Distribuicao::PADRAO,
+                Distribuicao::Classe(2),
+                Distribuicao::Classe(3),
+                Distribuicao::Classe(4),
+                Distribuicao::Classe(5),
+                Distribuicao::Normal {
+                    media: 50.0,
+                    desvio: 15.0,
+                },
+                Distribuicao::Exponencial { media: 50.0 },
+                Distribuicao::LogNormal {
+                    mu: 3.5,
+                    sigma: 0.8,
+                },
+                Distribuicao::Bimodal {
+                    curtas: (1, 20),
+                    longas: (80, 100),
+                    fracao_longas: 0.2,
+                },
+            ],
+            selected_distribuicao: 0,
+            objetivos: OBJETIVOS.to_vec(),
+            selected_objetivo: 0,
+            instancias_arquivo: Vec::new(),
+            selected_instancia_arquivo: 0,
+            precedencias: None,
+            reprogramacao: None,
             results: Vec::new(),
             current_exec: 0,
-            output_filename: "resultados.csv".to_string(),
+            tempo_execucoes: Duration::ZERO,
+            saida: ConfigSaida::new(),
+            edicao: None,
+            erro_salvar: None,
+            arquivo_config: config::ARQUIVO_PADRAO.to_string(),
+            valores_config: Vec::new(),
+            erro_config: None,
+            erro_execucao: None,
+            salvar_convergencia: false,
+            convergencia_filename: String::new(),
+            convergencia_atual: Vec::new(),
             perturbacao_state,
             max_iter_state,
             scroll_position: 0,
+            gantt_replicacao: 0,
+            gantt_ponto: None,
+            frente_ponto: 0,
+            frente_filename: String::new(),
+            configuracoes_comparadas: Vec::new(),
+            comparacao: None,
+            configuracao_atual: 0,
+            historico: None,
+            historico_fontes: Vec::new(),
+            erro_historico: None,
             should_quit: false,
-        }
+        };
+        app.valores_config = CampoTexto::TODOS
+            .iter()
+            .map(|&campo| app.texto_campo(campo))
+            .collect();
+        app
     }
 }
 
@@ -74,24 +423,7 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Resu
             break Ok(());
         }
 
-        terminal.draw(|f| {
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Min(0)])
-                .split(f.size());
-
-            match app.current_screen {
-                Screen::Menu => {
-                    render_menu(f, &app, chunks[0]);
-                }
-                Screen::Running => {
-                    render_running(f, &app, chunks[0]);
-                }
-                Screen::Results => {
-                    render_results(f, &app, chunks[0]);
-                }
-            }
-        })?;
+        terminal.draw(|f| desenhar(f, &app))?;
 
         if event::poll(Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()? {
@@ -99,8 +431,49 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Resu
             }
         }
 
-        if matches!(app.current_screen, Screen::Running) && app.current_exec < 10 {
-            execute_blm(&mut app);
+        if matches!(app.current_screen, Screen::Running) && app.current_exec < app.execucoes {
+            if app.comparacao.is_some() {
+                execute_comparacao(&mut app, terminal);
+            } else {
+                execute_blm(&mut app, terminal);
+            }
+        }
+    }
+}
+
+fn desenhar(f: &mut ratatui::Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0)])
+        .split(f.size());
+
+    match app.current_screen {
+        Screen::Menu => {
+            render_menu(f, app, chunks[0]);
+        }
+        Screen::Running => {
+            render_running(f, app, chunks[0]);
+        }
+        Screen::Results => {
+            render_results(f, app, chunks[0]);
+        }
+        Screen::Gantt => {
+            render_gantt(f, app, chunks[0]);
+        }
+        Screen::Frente => {
+            render_frente(f, app, chunks[0]);
+        }
+        Screen::Historico => {
+            render_historico(f, app, chunks[0]);
+        }
+        Screen::HistoricoDetalhe => {
+            render_historico_detalhe(f, app, chunks[0]);
+        }
+        Screen::HistoricoComparacao => {
+            render_historico_comparacao(f, app, chunks[0]);
+        }
+        Screen::Comparacao => {
+            render_comparacao(f, app, chunks[0]);
         }
     }
 }
@@ -114,7 +487,7 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
             Constraint::Length(5),
             Constraint::Length(5),
             Constraint::Length(if app.selected_algorithm == 1 { 7 } else { 0 }),
-            Constraint::Length(if app.selected_algorithm == 1 { 7 } else { 0 }),
+            Constraint::Length(if app.selected_algorithm != 0 { 7 } else { 0 }),
             Constraint::Min(0),
         ])
         .split(area);
@@ -122,14 +495,22 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
     let algorithm_names = [
         "Busca Local Monotônica - Melhor Melhora",
         "Busca Local Iterada",
+        "Busca Local de Pareto",
     ];
-    let title = Paragraph::new(algorithm_names[app.selected_algorithm])
-        .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )
-        .block(Block::default().borders(Borders::ALL));
+    let title = Paragraph::new(format!(
+        "{} — {}",
+        algorithm_names[app.selected_algorithm],
+        app.rotulo_objetivo()
+    ))
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Objetivo (C) | Prazos (P): {}",
+        app.prazos[app.selected_prazos].nome()
+    )));
     f.render_widget(title, menu_chunks[0]);
 
     let algo_items: Vec<ListItem> = algorithm_names
@@ -147,11 +528,11 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
         })
         .collect();
 
-    let algo_list = List::new(algo_items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("Algoritmo (Tab)"),
-    );
+    let algo_list =
+        List::new(algo_items).block(Block::default().borders(Borders::ALL).title(format!(
+            "Algoritmo (Tab) | Setup (U): {}",
+            app.setups[app.selected_setup].nome()
+        )));
     f.render_widget(algo_list, menu_chunks[1]);
 
     let m_items: Vec<ListItem> = app
@@ -172,37 +553,78 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
         .collect();
 
     let m_title = format!(
-        "Número de Máquinas (↑/↓) [{}/{}]",
+        "Número de Máquinas (↑/↓) [{}/{}] | Ambiente (V): {}",
         app.selected_m + 1,
-        app.m_values.len()
+        app.m_values.len(),
+        match app.instancia_arquivo() {
+            Some((caminho, instancia)) => format!(
+                "{caminho} [{}/{}] (n={}, m={})",
+                app.selected_instancia_arquivo + 1,
+                app.instancias_arquivo.len(),
+                instancia.n_tarefas(),
+                instancia.n_maquinas()
+            ),
+            None => app.ambientes[app.selected_ambiente].nome(),
+        }
     );
     let m_list = List::new(m_items).block(Block::default().borders(Borders::ALL).title(m_title));
-    f.render_widget(m_list, menu_chunks[2]);
+    f.render_stateful_widget(
+        m_list,
+        menu_chunks[2],
+        &mut ListState::default().with_selected(Some(app.selected_m)),
+    );
 
-    let r_items: Vec<ListItem> = app
-        .r_values
+    // Com n informado diretamente, a lista de tarefas toma o lugar da de replicação
+    let m = app.m_values[app.selected_m];
+    let (rotulos, selecionado, nome_lista): (Vec<String>, usize, &str) = if app.n_values.is_empty()
+    {
+        (
+            app.r_values
+                .iter()
+                .map(|r| format!("Replicação: {r} (n={})", (m as f64).powf(*r) as usize))
+                .collect(),
+            app.selected_r,
+            "Fator de Replicação",
+        )
+    } else {
+        (
+            app.n_values
+                .iter()
+                .map(|n| format!("Tarefas: {n}"))
+                .collect(),
+            app.selected_n,
+            "Número de Tarefas",
+        )
+    };
+    let r_items: Vec<ListItem> = rotulos
         .iter()
         .enumerate()
-        .map(|(i, r)| {
-            let style = if i == app.selected_r {
+        .map(|(i, rotulo)| {
+            let style = if i == selecionado {
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
-            let prefix = if i == app.selected_r { "► " } else { "  " };
-            ListItem::new(format!("{prefix}Replicação: {r}")).style(style)
+            let prefix = if i == selecionado { "► " } else { "  " };
+            ListItem::new(format!("{prefix}{rotulo}")).style(style)
         })
         .collect();
 
     let r_title = format!(
-        "Fator de Replicação (←/→) [{}/{}]",
-        app.selected_r + 1,
-        app.r_values.len()
+        "{nome_lista} (←/→) [{}/{}] | Elegibilidade (E): {} | Tempos (I): {}",
+        selecionado + 1,
+        rotulos.len(),
+        app.elegibilidades[app.selected_elegibilidade].nome(),
+        app.distribuicoes[app.selected_distribuicao].nome()
     );
     let r_list = List::new(r_items).block(Block::default().borders(Borders::ALL).title(r_title));
-    f.render_widget(r_list, menu_chunks[3]);
+    f.render_stateful_widget(
+        r_list,
+        menu_chunks[3],
+        &mut ListState::default().with_selected(Some(selecionado)),
+    );
 
     // Mostrar perturbação apenas se ILS estiver selecionado
     if app.selected_algorithm == 1 {
@@ -242,7 +664,8 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
             .collect();
 
         let pert_title = format!(
-            "Intensidade de Perturbação (W/S) [{}/{}] {}",
+            "Intensidade de Perturbação (W/S, X edita, Y: {}) [{}/{}] {}",
+            app.adaptacao.nome(),
             app.selected_perturbacao + 1,
             app.perturbacao_values.len(),
             if app.perturbacao_values.len() > 1 {
@@ -268,6 +691,15 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
             menu_chunks[4],
             &mut app.perturbacao_state.clone(),
         );
+    }
+
+    // Na busca de Pareto o critério de parada é o número de soluções exploradas
+    if app.selected_algorithm != 0 {
+        let rotulo_parada = if app.pareto() {
+            "Máx. Explorações"
+        } else {
+            "Max Iter s/ Melhora"
+        };
 
         let max_iter_items: Vec<ListItem> = app
             .max_iter_values
@@ -299,13 +731,12 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
                 } else {
                     ""
                 };
-                ListItem::new(format!("{prefix}Max Iter s/ Melhora: {val}{scroll_hint}"))
-                    .style(style)
+                ListItem::new(format!("{prefix}{rotulo_parada}: {val}{scroll_hint}")).style(style)
             })
             .collect();
 
         let max_iter_title = format!(
-            "Critério de Parada (A/D) [{}/{}] {}",
+            "Critério de Parada (A/D, Z edita) [{}/{}] {}",
             app.selected_max_iter + 1,
             app.max_iter_values.len(),
             if app.max_iter_values.len() > 1 {
@@ -333,37 +764,381 @@ fn render_menu(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
         );
     }
 
-    let help_idx = if app.selected_algorithm == 1 { 6 } else { 4 };
-    let help = Paragraph::new(vec![
-        Line::from("Pressione ENTER para executar | Q para sair"),
+    let mut help_lines = vec![
+        Line::from(
+            "Pressione ENTER para executar | T para convergência | L para formato | Q para sair",
+        ),
+        Line::from("O: diretório | F: arquivo | J: JSON Lines | B: SQLite"),
+        Line::from("M: máquinas | R: replicação | N: tarefas (n direto) | K: execuções"),
+        Line::from("H: histórico de resultados | +/-: adiciona/remove configuração | =: compara"),
         Line::from(Span::styled(
-            format!("Arquivo de saída: {}", app.output_filename),
+            format!(
+                "Saída: {} (CSV {})",
+                app.saida.descrever(),
+                app.saida.formato_csv.nome()
+            ),
             Style::default().fg(Color::Gray),
         )),
-    ])
-    .style(Style::default().fg(Color::Green))
-    .block(Block::default().borders(Borders::ALL));
-    f.render_widget(help, menu_chunks[help_idx]);
+        Line::from(Span::styled(
+            format!(
+                "Execuções por configuração: {} | Configuração: {}",
+                app.execucoes, app.arquivo_config
+            ),
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "Salvar convergência: {}",
+                if app.salvar_convergencia {
+                    "sim"
+                } else {
+                    "não"
+                }
+            ),
+            Style::default().fg(Color::Gray),
+        )),
+    ];
+    if !app.configuracoes_comparadas.is_empty() {
+        let nomes: Vec<String> = app
+            .configuracoes_comparadas
+            .iter()
+            .map(Configuracao::nome)
+            .collect();
+        help_lines.push(Line::from(Span::styled(
+            format!(
+                "Comparação: {}{}",
+                nomes.join(" × "),
+                if nomes.len() < 2 {
+                    " (adicione ao menos mais uma)"
+                } else {
+                    ""
+                }
+            ),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    if let Some(erro) = &app.erro_historico {
+        help_lines.push(Line::from(Span::styled(
+            format!("Histórico: {erro}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    if let Some(erro) = &app.erro_config {
+        help_lines.push(Line::from(Span::styled(
+            format!("Configuração não salva: {erro}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    if let Some((caminho, arcos)) = &app.precedencias {
+        help_lines.push(Line::from(Span::styled(
+            format!("Precedências: {caminho} ({} arcos)", arcos.len()),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    if let Some(reprogramacao) = &app.reprogramacao {
+        help_lines.push(Line::from(Span::styled(
+            format!(
+                "Reprogramação: {} | Máx. migrações: {} | Penalidade: {}",
+                reprogramacao.caminho,
+                reprogramacao
+                    .orcamento
+                    .map_or("sem limite".to_string(), |k| k.to_string()),
+                reprogramacao.penalidade
+            ),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    if let Some(edicao) = &app.edicao {
+        let rotulo = match edicao.campo {
+            CampoTexto::Diretorio => "Diretório de saída",
+            CampoTexto::Arquivo => "Arquivo de saída",
+            CampoTexto::Maquinas => "Máquinas (lista ou faixa a..b:passo)",
+            CampoTexto::Replicacoes => "Fatores de replicação (lista ou faixa a..b:passo)",
+            CampoTexto::Tarefas => "Tarefas (lista ou faixa a..b:passo; vazio usa n = m^r)",
+            CampoTexto::Perturbacoes => "Perturbações (frações em (0, 1], lista ou faixa)",
+            CampoTexto::Adaptacao => "Adaptação da perturbação (fixa, escalonada ou reativa)",
+            CampoTexto::MaxIteracoes => "Critérios de parada (lista ou faixa a..b:passo)",
+            CampoTexto::Execucoes => "Execuções por configuração",
+            CampoTexto::FiltroHistorico => "Filtro",
+        };
+        help_lines.push(Line::from(Span::styled(
+            format!(
+                "{rotulo}: {}█  (ENTER confirma | ESC cancela)",
+                edicao.buffer
+            ),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        if let Some(erro) = &edicao.erro {
+            help_lines.push(Line::from(Span::styled(
+                format!("Valor recusado: {erro}"),
+                Style::default().fg(Color::Red),
+            )));
+        }
+    }
+    let help = Paragraph::new(help_lines)
+        .style(Style::default().fg(Color::Green))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help, menu_chunks[6]);
+}
+
+fn formatar_duracao(duracao: Duration) -> String {
+    let segundos = duracao.as_secs_f64();
+    if segundos < 60.0 {
+        format!("{segundos:.1}s")
+    } else if segundos < 3600.0 {
+        format!(
+            "{}min{:02}s",
+            duracao.as_secs() / 60,
+            duracao.as_secs() % 60
+        )
+    } else {
+        format!(
+            "{}h{:02}min",
+            duracao.as_secs() / 3600,
+            duracao.as_secs() % 3600 / 60
+        )
+    }
 }
 
 fn render_running(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
-    let text = vec![
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(9), Constraint::Min(0)])
+        .split(area);
+
+    let ultimo = app.convergencia_atual.last();
+    let mut text = vec![
         Line::from(Span::styled(
-            "Executando algoritmo...",
+            match &app.comparacao {
+                Some(comparacao) => format!(
+                    "Comparando configurações... {} ({}/{})",
+                    comparacao.configuracoes[app.configuracao_atual].nome(),
+                    app.configuracao_atual + 1,
+                    comparacao.configuracoes.len()
+                ),
+                None => "Executando algoritmo...".to_string(),
+            },
             Style::default().fg(Color::Yellow),
         )),
-        Line::from(format!("Execução: {}/10", app.current_exec)),
-        Line::from(""),
-        Line::from(format!("Salvando em: {}", app.output_filename)),
-        Line::from(""),
-        Line::from("Pressione Q para cancelar"),
+        Line::from(format!(
+            "Execução: {}/{} ({:.0}% concluído) | Restante estimado: {}",
+            app.current_exec + 1,
+            app.execucoes,
+            100.0 * app.current_exec as f64 / app.execucoes as f64,
+            app.restante_estimado()
+                .map_or("calculando...".to_string(), formatar_duracao)
+        )),
+        Line::from(match ultimo {
+            Some(p) => format!(
+                "Iteração: {} | Tempo: {:.0}ms | {} atual: {} | Melhor: {}{}",
+                p.iteracao,
+                p.tempo_ms,
+                if app.pareto() {
+                    "Makespan".to_string()
+                } else {
+                    app.objetivo().rotulo()
+                },
+                formatar_valor(p.valor_atual),
+                formatar_valor(p.valor_melhor),
+                if p.perturbacao > 0.0 {
+                    format!(" | Perturbação: {:.3}", p.perturbacao)
+                } else {
+                    String::new()
+                }
+            ),
+            None => String::new(),
+        }),
+        Line::from(format!("Salvando em: {}", app.saida.descrever())),
     ];
+    if app.salvar_convergencia {
+        text.push(Line::from(format!(
+            "Convergência em: {}",
+            app.convergencia_filename
+        )));
+    }
+    text.extend(vec![
+        Line::from(""),
+        Line::from("Pressione Q ou ESC para cancelar (a execução em andamento é descartada)"),
+    ]);
     let paragraph =
         Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Progresso"));
+    f.render_widget(paragraph, chunks[0]);
+
+    render_convergencia(
+        f,
+        &app.convergencia_atual,
+        &if app.pareto() {
+            "makespan".to_string()
+        } else {
+            app.objetivo().nome()
+        },
+        chunks[1],
+    );
+}
+
+fn render_convergencia(
+    f: &mut ratatui::Frame,
+    convergencia: &[PontoConvergencia],
+    eixo: &str,
+    area: ratatui::layout::Rect,
+) {
+    let atual: Vec<(f64, f64)> = convergencia
+        .iter()
+        .map(|p| (p.iteracao as f64, p.valor_atual))
+        .collect();
+    let melhor: Vec<(f64, f64)> = convergencia
+        .iter()
+        .map(|p| (p.iteracao as f64, p.valor_melhor))
+        .collect();
+
+    let x_max = convergencia.last().map_or(1, |p| p.iteracao.max(1)) as f64;
+    let y_min = convergencia
+        .iter()
+        .map(|p| p.valor_melhor)
+        .reduce(f64::min)
+        .unwrap_or(0.0);
+    let y_max = convergencia
+        .iter()
+        .map(|p| p.valor_atual.max(p.valor_melhor))
+        .reduce(f64::max)
+        .unwrap_or(1.0);
+    let y_max = if y_max > y_min { y_max } else { y_min + 1.0 };
+
+    let datasets = vec![
+        Dataset::default()
+            .name("atual")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(&atual),
+        Dataset::default()
+            .name("melhor")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(&melhor),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title("Convergência"))
+        .x_axis(
+            Axis::default()
+                .title("iteração")
+                .bounds([0.0, x_max])
+                .labels(vec![Span::raw("0"), Span::raw(format!("{x_max:.0}"))]),
+        )
+        .y_axis(
+            Axis::default()
+                .title(eixo.to_string())
+                .bounds([y_min, y_max])
+                .labels(vec![
+                    Span::raw(format!("{y_min:.0}")),
+                    Span::raw(format!("{y_max:.0}")),
+                ]),
+        );
+    f.render_widget(chart, area);
+}
+
+fn linha_resumo(rotulo: &str, resumo: &Resumo, casas: usize) -> Line<'static> {
+    Line::from(format!(
+        "{rotulo:<18}{:>12.casas$}{:>12.casas$}{:>12.casas$}{:>12.casas$}{:>12.casas$}",
+        resumo.min, resumo.media, resumo.mediana, resumo.max, resumo.desvio
+    ))
+}
+
+fn render_resumo(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let mut linhas: Vec<Line> = Vec::new();
+    for (configuracao, grupo) in agrupar_por_configuracao(&app.results) {
+        let coletar = |valor: &dyn Fn(&Result) -> f64| -> Vec<f64> {
+            grupo.iter().map(|r| valor(r)).collect()
+        };
+        linhas.push(Line::from(Span::styled(
+            format!("{configuracao} | {} execuções", grupo.len()),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        linhas.push(Line::from(Span::styled(
+            format!(
+                "{:<18}{:>12}{:>12}{:>12}{:>12}{:>12}",
+                "", "mín", "média", "mediana", "máx", "desvio"
+            ),
+            Style::default().fg(Color::Gray),
+        )));
+        linhas.push(linha_resumo(
+            "Valor final",
+            &resumir(&coletar(&|r| r.valor_final)),
+            1,
+        ));
+        linhas.push(linha_resumo(
+            "Tempo (ms)",
+            &resumir(&coletar(&|r| r.tempo_exec)),
+            2,
+        ));
+        linhas.push(linha_resumo(
+            "Iterações",
+            &resumir(&coletar(&|r| r.iteracoes as f64)),
+            1,
+        ));
+        linhas.push(linha_resumo(
+            "Melhoria (%)",
+            &resumir(&coletar(&melhoria_relativa)),
+            2,
+        ));
+        let gaps: Vec<f64> = grupo
+            .iter()
+            .filter_map(|r| gap_limite_inferior(r))
+            .collect();
+        if gaps.is_empty() {
+            linhas.push(Line::from(format!(
+                "{:<18}{:>12}",
+                "Gap LB (%)", "NA (só makespan)"
+            )));
+        } else {
+            linhas.push(linha_resumo("Gap LB (%)", &resumir(&gaps), 2));
+        }
+        let desvios: Vec<f64> = grupo
+            .iter()
+            .filter_map(|r| desvio_melhor_conhecido(r))
+            .collect();
+        if !desvios.is_empty() {
+            linhas.push(linha_resumo("Desvio BK (%)", &resumir(&desvios), 2));
+        }
+    }
+
+    let paragraph = Paragraph::new(linhas).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Resumo por configuração"),
+    );
     f.render_widget(paragraph, area);
 }
 
 fn render_results(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    // Cada configuração ocupa 7 linhas no painel de resumo, 8 com melhor conhecido
+    let altura_resumo = agrupar_por_configuracao(&app.results)
+        .iter()
+        .map(|(_, grupo)| {
+            if grupo.iter().any(|r| desvio_melhor_conhecido(r).is_some()) {
+                8
+            } else {
+                7
+            }
+        })
+        .sum::<u16>()
+        + 2;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(altura_resumo.min(area.height / 2)),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    render_resumo(f, app, chunks[0]);
+    let area = chunks[1];
+
     let results_text: Vec<Line> = app
         .results
         .iter()
@@ -374,11 +1149,13 @@ fn render_results(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect
                 Line::from(format!("Algoritmo: {}", r.algoritmo)),
                 Line::from(format!(
                     "Tarefas: {} | Máquinas: {} | Replicação: {}",
-                    r.n_tarefas, r.n_maquinas, r.replicacao
+                    r.n_tarefas,
+                    r.n_maquinas,
+                    r.replicacao.map_or("NA".to_string(), |r| r.to_string())
                 )),
             ];
             if r.perturbacao > 0.0 {
-                lines.push(Line::from(format!("Perturbação: {:.1}", r.perturbacao)));
+                lines.push(Line::from(format!("Perturbação: {}", r.perturbacao)));
             }
             lines.extend(vec![
                 Line::from(format!(
@@ -386,24 +1163,70 @@ fn render_results(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect
                     r.tempo_exec, r.iteracoes
                 )),
                 Line::from(format!(
-                    "Makespan: {} → {}",
-                    r.makespan_inicial, r.makespan_final
+                    "{}: {} → {}",
+                    r.objetivo,
+                    formatar_valor(r.valor_inicial),
+                    formatar_valor(r.valor_final)
                 )),
-                Line::from(""),
             ]);
+            if !r.frente.is_empty() {
+                lines.push(Line::from(format!(
+                    "Frente: {} pontos | Hipervolume: {:.4}",
+                    r.frente.len(),
+                    r.hipervolume.unwrap_or(0.0)
+                )));
+            }
+            if let Some(referencia) = &r.referencia {
+                let mut linha = format!("Instância: {}", referencia.nome);
+                if let Some(melhor) = referencia.melhor_conhecido {
+                    linha.push_str(&format!(
+                        " | {}: {}",
+                        if referencia.otimo {
+                            "Ótimo"
+                        } else {
+                            "Melhor conhecido"
+                        },
+                        formatar_valor(melhor)
+                    ));
+                }
+                if let Some(desvio) = desvio_melhor_conhecido(r) {
+                    linha.push_str(&format!(" | Desvio: {desvio:.2}%"));
+                }
+                lines.push(Line::from(linha));
+            }
+            if let Some(migracoes) = r.migracoes {
+                lines.push(Line::from(format!("Migrações: {migracoes}")));
+            }
+            lines.push(Line::from(""));
             lines
         })
+        .chain(app.erro_execucao.iter().map(|erro| {
+            Line::from(Span::styled(
+                format!("Execução interrompida: {erro}"),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ))
+        }))
         .chain(vec![
             Line::from(""),
-            Line::from(Span::styled(
-                format!("Resultados salvos em: {}", app.output_filename),
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )),
+            match &app.erro_salvar {
+                Some(erro) => Line::from(Span::styled(
+                    format!("Erro ao salvar {erro}"),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )),
+                None => Line::from(Span::styled(
+                    format!("Resultados salvos em: {}", app.saida.descrever()),
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                )),
+            },
             Line::from(""),
             Line::from(Span::styled(
-                "Pressione ↑/↓ para rolar | ENTER para voltar ao menu",
+                if app.results.iter().any(|r| !r.frente.is_empty()) {
+                    "Pressione ↑/↓ para rolar | G para Gantt | P para frente de Pareto | ENTER para voltar ao menu"
+                } else {
+                    "Pressione ↑/↓ para rolar | G para Gantt | ENTER para voltar ao menu"
+                },
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
@@ -442,73 +1265,849 @@ fn render_results(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect
     f.render_widget(paragraph, area);
 }
 
+const GANTT_CORES: [Color; 6] = [
+    Color::Blue,
+    Color::Green,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Yellow,
+    Color::Red,
+];
+
+fn render_gantt(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let Some(r) = app.results.get(app.gantt_replicacao) else {
+        return;
+    };
+    let alocacao = app
+        .gantt_ponto
+        .and_then(|p| r.frente.get(p))
+        .map_or(&r.alocacao, |p| &p.alocacao);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(area);
+
+    // O objetivo otimizado pode não ser o makespan; o Gantt sempre mostra o término
+    let makespan = alocacao
+        .iter()
+        .filter_map(|tarefas| tarefas.last())
+        .map(|t| t.fim)
+        .fold(0.0, f64::max);
+    let gap = if r.limite_inferior > 0.0 {
+        (makespan - r.limite_inferior) / r.limite_inferior * 100.0
+    } else {
+        0.0
+    };
+    let header = Paragraph::new(vec![
+        Line::from(format!(
+            "{} | Tarefas: {} | Máquinas: {}",
+            r.algoritmo, r.n_tarefas, r.n_maquinas
+        )),
+        Line::from(vec![
+            Span::styled(
+                format!("Makespan: {} ┃", formatar_valor(makespan)),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("Limite inferior: {} ┊", formatar_valor(r.limite_inferior)),
+                Style::default().fg(Color::White),
+            ),
+            Span::raw(format!("  Gap: {gap:.2}%")),
+        ]),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(match app.gantt_ponto {
+                Some(p) => format!(
+                    "Gantt - Execução {}/{} - Ponto {}/{} da frente",
+                    app.gantt_replicacao + 1,
+                    app.results.len(),
+                    p + 1,
+                    r.frente.len()
+                ),
+                None => format!(
+                    "Gantt - Execução {}/{}",
+                    app.gantt_replicacao + 1,
+                    app.results.len()
+                ),
+            }),
+    );
+    f.render_widget(header, chunks[0]);
+
+    // Rótulo "M00 " + barras + 1 coluna para o marcador do makespan
+    let rotulo = 4;
+    let largura = chunks[1].width.saturating_sub(2 + rotulo + 1).max(1) as usize;
+    let horizonte = makespan.max(r.limite_inferior).max(1.0);
+    let coluna = |t: f64| ((t / horizonte) * largura as f64).round() as usize;
+    let col_lb = coluna(r.limite_inferior).min(largura);
+    let col_ms = coluna(makespan).min(largura);
+
+    let mut linhas: Vec<Line> = alocacao
+        .iter()
+        .enumerate()
+        .map(|(i, tarefas)| {
+            // Cada célula da barra recebe o caractere e o estilo da tarefa que a ocupa
+            let mut celulas: Vec<(char, Style)> = vec![(' ', Style::default()); largura + 1];
+            for alocada in tarefas {
+                let (c0, c1) = (
+                    coluna(alocada.inicio).min(largura),
+                    coluna(alocada.fim).min(largura),
+                );
+                let estilo = Style::default()
+                    .bg(GANTT_CORES[alocada.tarefa % GANTT_CORES.len()])
+                    .fg(Color::Black);
+                let texto: Vec<char> = alocada.tarefa.to_string().chars().collect();
+                for (k, celula) in celulas[c0..c1].iter_mut().enumerate() {
+                    let ch = if c1 - c0 > texto.len() {
+                        texto.get(k)
+                    } else {
+                        None
+                    };
+                    *celula = (*ch.unwrap_or(&' '), estilo);
+                }
+            }
+            celulas[col_lb].0 = '┊';
+            if celulas[col_lb].1.bg.is_none() {
+                celulas[col_lb].1 = Style::default().fg(Color::White);
+            }
+            celulas[col_ms] = ('┃', Style::default().fg(Color::Red));
+
+            let mut spans = vec![Span::raw(format!("M{i:<2} "))];
+            spans.extend(
+                celulas
+                    .into_iter()
+                    .map(|(ch, estilo)| Span::styled(ch.to_string(), estilo)),
+            );
+            Line::from(spans)
+        })
+        .collect();
+
+    linhas.push(Line::from(""));
+    linhas.push(Line::from(Span::styled(
+        "←/→ troca a execução | ↑/↓ para rolar | G/ESC para voltar",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )));
+
+    let paragraph = Paragraph::new(linhas)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Carga por máquina"),
+        )
+        .scroll((app.scroll_position, 0));
+    f.render_widget(paragraph, chunks[1]);
+}
+
+fn render_frente(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let Some(r) = app.results.get(app.gantt_replicacao) else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(area);
+    let header = Paragraph::new(vec![
+        Line::from(format!(
+            "{} | Tarefas: {} | Máquinas: {} | Pontos: {}",
+            r.algoritmo,
+            r.n_tarefas,
+            r.n_maquinas,
+            r.frente.len()
+        )),
+        Line::from(format!(
+            "Hipervolume (normalizado entre o limite inferior e a escala de referência): {:.4}",
+            r.hipervolume.unwrap_or(0.0)
+        )),
+    ])
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Frente de Pareto - Execução {}/{}",
+        app.gantt_replicacao + 1,
+        app.results.len()
+    )));
+    f.render_widget(header, chunks[0]);
+
+    let corpo = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(44), Constraint::Min(0)])
+        .split(chunks[1]);
+
+    let mut itens: Vec<ListItem> = r
+        .frente
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let (prefixo, estilo) = if i == app.frente_ponto {
+                (
+                    "► ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default())
+            };
+            ListItem::new(format!(
+                "{prefixo}{:>9} {:>12.2} {:>6}",
+                formatar_valor(p.makespan),
+                p.variancia,
+                p.migracoes
+            ))
+            .style(estilo)
+        })
+        .collect();
+    itens.insert(
+        0,
+        ListItem::new(format!(
+            "  {:>9} {:>12} {:>6}",
+            "Cmax", "variância", "migr."
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+    );
+    // Mantém o ponto selecionado visível
+    let mut estado = ListState::default();
+    estado.select(Some(app.frente_ponto + 1));
+    let lista = List::new(itens).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("↑/↓ ponto | ←/→ execução | G Gantt | ESC"),
+    );
+    f.render_stateful_widget(lista, corpo[0], &mut estado);
+
+    let pontos: Vec<(f64, f64)> = r.frente.iter().map(|p| (p.makespan, p.variancia)).collect();
+    let selecionado: Vec<(f64, f64)> = pontos.get(app.frente_ponto).copied().into_iter().collect();
+    let limites = |valores: &mut dyn Iterator<Item = f64>| {
+        let (menor, maior) = valores.fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| {
+            (a.min(v), b.max(v))
+        });
+        if maior > menor {
+            [menor, maior]
+        } else {
+            [menor - 1.0, menor + 1.0]
+        }
+    };
+    let x = limites(&mut pontos.iter().map(|p| p.0));
+    let y = limites(&mut pontos.iter().map(|p| p.1));
+    let datasets = vec![
+        Dataset::default()
+            .name("frente")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(Color::Green))
+            .data(&pontos),
+        Dataset::default()
+            .name("selecionado")
+            .marker(symbols::Marker::Block)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&selecionado),
+    ];
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Makespan × variância das cargas"),
+        )
+        .x_axis(Axis::default().title("makespan").bounds(x).labels(vec![
+            Span::raw(formatar_valor(x[0])),
+            Span::raw(formatar_valor(x[1])),
+        ]))
+        .y_axis(Axis::default().title("variância").bounds(y).labels(vec![
+            Span::raw(format!("{:.0}", y[0])),
+            Span::raw(format!("{:.0}", y[1])),
+        ]));
+    f.render_widget(chart, corpo[1]);
+}
+
+fn render_historico(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let Some(historico) = &app.historico else {
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(5)])
+        .split(area);
+
+    let seta = if historico.crescente { " ▲" } else { " ▼" };
+    let colunas = [
+        ("Data", Some(historico::Ordem::Data)),
+        ("Algoritmo", Some(historico::Ordem::Algoritmo)),
+        ("n", Some(historico::Ordem::N)),
+        ("m", Some(historico::Ordem::M)),
+        ("Parâmetro", Some(historico::Ordem::Parametro)),
+        ("Parada", None),
+        ("Ambiente", None),
+        ("Objetivo", None),
+        ("Valor", Some(historico::Ordem::Valor)),
+        ("Tempo(ms)", Some(historico::Ordem::Tempo)),
+    ];
+    let cabecalho = Row::new(std::iter::once(Cell::from("")).chain(colunas.iter().map(
+        |(nome, ordem)| {
+            if *ordem == Some(historico.ordem) {
+                Cell::from(format!("{nome}{seta}"))
+            } else {
+                Cell::from(*nome)
+            }
+        },
+    )))
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+    let linhas: Vec<Row> = historico
+        .visiveis
+        .iter()
+        .map(|&i| {
+            let r = &historico.registros[i];
+            let marca = match historico.selecionados.iter().position(|&s| s == i) {
+                Some(pos) => format!("●{}", pos + 1),
+                None => String::new(),
+            };
+            Row::new(vec![
+                marca,
+                r.timestamp.clone(),
+                r.algoritmo.clone(),
+                r.n.to_string(),
+                r.m.to_string(),
+                r.parametro.clone(),
+                r.max_iter.clone(),
+                r.ambiente.clone(),
+                r.objetivo.clone(),
+                formatar_valor(r.valor),
+                format!("{:.2}", r.tempo_ms),
+            ])
+        })
+        .collect();
+    let larguras = [
+        Constraint::Length(3),
+        Constraint::Length(20),
+        Constraint::Length(10),
+        Constraint::Length(7),
+        Constraint::Length(5),
+        Constraint::Length(11),
+        Constraint::Length(7),
+        Constraint::Min(12),
+        Constraint::Length(14),
+        Constraint::Length(10),
+        Constraint::Length(11),
+    ];
+    let tabela = Table::new(linhas, larguras)
+        .header(cabecalho)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("► ")
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Histórico — {}/{} resultados | Ordem: {}{seta} | Filtro: {} | Marcados: {}",
+            historico.visiveis.len(),
+            historico.registros.len(),
+            historico.ordem.nome(),
+            if historico.filtro.is_empty() {
+                "nenhum"
+            } else {
+                &historico.filtro
+            },
+            historico.selecionados.len()
+        )));
+    f.render_stateful_widget(
+        tabela,
+        chunks[0],
+        &mut TableState::default().with_selected(Some(historico.cursor)),
+    );
+
+    let mut ajuda = vec![
+        Line::from(
+            "↑/↓ PgUp/PgDn navegar | ENTER detalhes | ESPAÇO marca | C compara marcados | \
+             O ordena | I inverte | / filtro (alg= n= m= p= amb= obj= ou texto) | R recarrega | Q volta",
+        ),
+        Line::from(Span::styled(
+            format!("Fontes: {}", historico.fontes.join(", ")),
+            Style::default().fg(Color::Gray),
+        )),
+    ];
+    if let Some(edicao) = &app.edicao {
+        ajuda.push(Line::from(Span::styled(
+            format!("Filtro: {}█  (ENTER confirma | ESC cancela)", edicao.buffer),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        if let Some(erro) = &edicao.erro {
+            ajuda[1] = Line::from(Span::styled(
+                format!("Filtro recusado: {erro}"),
+                Style::default().fg(Color::Red),
+            ));
+        }
+    }
+    let ajuda = Paragraph::new(ajuda)
+        .style(Style::default().fg(Color::Green))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(ajuda, chunks[1]);
+}
+
+fn render_historico_detalhe(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let Some(historico) = &app.historico else {
+        return;
+    };
+    let Some(r) = historico.atual().map(|i| &historico.registros[i]) else {
+        return;
+    };
+    let mut linhas = vec![Line::from(Span::styled(
+        format!("Arquivo: {}", r.arquivo),
+        Style::default().fg(Color::Gray),
+    ))];
+    linhas.extend(r.campos.iter().map(|(campo, valor)| {
+        Line::from(vec![
+            Span::styled(format!("{campo:>20}: "), Style::default().fg(Color::Cyan)),
+            Span::raw(valor.clone()),
+        ])
+    }));
+    let detalhe = Paragraph::new(linhas).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Resultado — ESC volta"),
+    );
+    f.render_widget(detalhe, area);
+}
+
+/// Registros marcados lado a lado, com a diferença de valor em relação ao primeiro marcado.
+fn render_historico_comparacao(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let Some(historico) = &app.historico else {
+        return;
+    };
+    let registros: Vec<&historico::Registro> = historico
+        .selecionados
+        .iter()
+        .map(|&i| &historico.registros[i])
+        .collect();
+    let Some(base) = registros.first() else {
+        return;
+    };
+    let melhor = registros
+        .iter()
+        .map(|r| r.valor)
+        .fold(f64::INFINITY, f64::min);
+
+    let mut nomes: Vec<&str> = Vec::new();
+    for r in &registros {
+        for (campo, _) in &r.campos {
+            if !nomes.contains(&campo.as_str()) {
+                nomes.push(campo);
+            }
+        }
+    }
+
+    let destaque = Style::default()
+        .fg(Color::Green)
+        .add_modifier(Modifier::BOLD);
+    let mut linhas = vec![Row::new(
+        std::iter::once(Cell::from("Δ valor vs. #1")).chain(registros.iter().map(|r| {
+            let celula = Cell::from(format!(
+                "{:+.2}%",
+                100.0 * (r.valor - base.valor) / base.valor.abs().max(f64::EPSILON)
+            ));
+            if r.valor == melhor {
+                celula.style(destaque)
+            } else {
+                celula
+            }
+        })),
+    )];
+    linhas.extend(nomes.iter().map(|nome| {
+        Row::new(
+            std::iter::once(Cell::from(nome.to_string())).chain(registros.iter().map(|r| {
+                let celula = Cell::from(r.campo(nome).unwrap_or("—").to_string());
+                if *nome == "valor" && r.valor == melhor {
+                    celula.style(destaque)
+                } else {
+                    celula
+                }
+            })),
+        )
+    }));
+
+    let mut larguras = vec![Constraint::Length(18)];
+    larguras.extend(registros.iter().map(|_| Constraint::Min(12)));
+    let cabecalho = Row::new(
+        std::iter::once("Campo".to_string()).chain((1..=registros.len()).map(|i| format!("#{i}"))),
+    )
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+    let tabela = Table::new(linhas, larguras).header(cabecalho).block(
+        Block::default().borders(Borders::ALL).title(format!(
+            "Comparação de {} resultados marcados — melhor valor em verde | ESC volta",
+            registros.len()
+        )),
+    );
+    f.render_widget(tabela, area);
+}
+
+fn render_comparacao(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let Some(comparacao) = &app.comparacao else {
+        return;
+    };
+    let k = comparacao.configuracoes.len();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(k as u16 + 3), Constraint::Min(0)])
+        .split(area);
+    let negrito = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+
+    let placares: Vec<Row> = comparacao
+        .configuracoes
+        .iter()
+        .enumerate()
+        .map(|(j, configuracao)| {
+            let placar = comparacao.placar(j);
+            let (confronto, diferenca, p_valor) = if j == 0 {
+                ("referência".to_string(), "—".to_string(), "—".to_string())
+            } else {
+                (
+                    format!("{}/{}/{}", placar.vitorias, placar.empates, placar.derrotas),
+                    format!("{:+.2}", placar.diferenca_gap),
+                    placar
+                        .p_valor
+                        .map_or("NA".to_string(), |p| format!("{p:.4}")),
+                )
+            };
+            Row::new(vec![
+                format!("#{} {}", j + 1, configuracao.nome()),
+                confronto,
+                formatar_valor(placar.media),
+                format!("{:.2}", placar.gap_medio),
+                diferenca,
+                p_valor,
+                format!("{:.2}", placar.tempo_medio),
+            ])
+        })
+        .collect();
+    let resumo = Table::new(
+        placares,
+        [
+            Constraint::Min(24),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(18),
+            Constraint::Length(12),
+            Constraint::Length(14),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Configuração",
+            "V/E/D vs #1",
+            "Média",
+            "Gap médio %",
+            "Δ gap vs #1 (pp)",
+            "p Wilcoxon",
+            "Tempo médio ms",
+        ])
+        .style(negrito),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Comparação — {} replicações nas mesmas instâncias | {} | gap ao melhor da replicação",
+        comparacao.valores.len(),
+        app.objetivo().rotulo()
+    )));
+    f.render_widget(resumo, chunks[0]);
+
+    let linhas: Vec<Row> = comparacao
+        .valores
+        .iter()
+        .enumerate()
+        .map(|(i, valores)| {
+            let melhor = valores.iter().copied().fold(f64::INFINITY, f64::min);
+            let celulas = valores.iter().enumerate().map(|(j, v)| {
+                let diferenca = v - valores[0];
+                let texto = if j == 0 {
+                    formatar_valor(*v)
+                } else {
+                    format!(
+                        "{} ({}{})",
+                        formatar_valor(*v),
+                        if diferenca > 0.0 { "+" } else { "" },
+                        formatar_valor(diferenca)
+                    )
+                };
+                if *v == melhor {
+                    Cell::from(texto).style(Style::default().fg(Color::Green))
+                } else {
+                    Cell::from(texto)
+                }
+            });
+            Row::new(
+                [
+                    Cell::from((i + 1).to_string()),
+                    Cell::from(comparacao.sementes[i].to_string()),
+                ]
+                .into_iter()
+                .chain(celulas),
+            )
+        })
+        .collect();
+    let mut larguras = vec![Constraint::Length(5), Constraint::Length(21)];
+    larguras.extend((0..k).map(|_| Constraint::Min(14)));
+    let cabecalho = Row::new(
+        ["Rep.".to_string(), "Semente".to_string()]
+            .into_iter()
+            .chain((1..=k).map(|j| format!("#{j} (Δ vs #1)"))),
+    )
+    .style(negrito);
+    let tabela = Table::new(linhas, larguras)
+        .header(cabecalho)
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Valores por replicação, melhor em verde — ↑/↓ rola | ESC volta"),
+        );
+    f.render_stateful_widget(
+        tabela,
+        chunks[1],
+        &mut TableState::default().with_selected(Some(app.scroll_position as usize)),
+    );
+}
+
+/// Lê os CSVs do diretório de saída e das fontes extras; em caso de erro, fica no menu.
+fn abrir_historico(app: &mut App) {
+    let mut fontes = vec![app.saida.diretorio.clone()];
+    for fonte in &app.historico_fontes {
+        if !fontes.contains(fonte) {
+            fontes.push(fonte.clone());
+        }
+    }
+    match historico::carregar(&fontes) {
+        Ok(mut historico) => {
+            // Ao recarregar, mantém a ordenação e o filtro; as marcações se perdem
+            if let Some(anterior) = app.historico.take() {
+                historico.ordem = anterior.ordem;
+                historico.crescente = anterior.crescente;
+                let _ = historico.filtrar(&anterior.filtro);
+            }
+            app.historico = Some(historico);
+            app.erro_historico = None;
+            app.current_screen = Screen::Historico;
+        }
+        Err(e) => {
+            app.erro_historico = Some(e.to_string());
+            app.current_screen = Screen::Menu;
+        }
+    }
+}
+
+/// Prepara uma nova rodada de execuções; a comparação, se houver, é definida depois.
+fn iniciar_execucao(app: &mut App) {
+    app.current_screen = Screen::Running;
+    app.results.clear();
+    app.comparacao = None;
+    app.current_exec = 0;
+    app.tempo_execucoes = Duration::ZERO;
+    app.scroll_position = 0;
+    app.convergencia_atual.clear();
+    app.erro_salvar = None;
+    app.erro_execucao = None;
+    let inicio = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    app.convergencia_filename = format!("convergencia_{inicio}.csv");
+    app.frente_filename = format!("frente_{inicio}.csv");
+}
+
+fn abrir_edicao(app: &mut App, campo: CampoTexto) {
+    app.edicao = Some(Edicao {
+        campo,
+        buffer: app.texto_campo(campo),
+        erro: None,
+    });
+}
+
+fn handle_edicao(app: &mut App, key_code: KeyCode) {
+    let Some(edicao) = app.edicao.as_mut() else {
+        return;
+    };
+    match key_code {
+        KeyCode::Char(c) => {
+            edicao.buffer.push(c);
+            edicao.erro = None;
+        }
+        KeyCode::Backspace => {
+            edicao.buffer.pop();
+            edicao.erro = None;
+        }
+        KeyCode::Esc => app.edicao = None,
+        KeyCode::Enter => {
+            let campo = edicao.campo;
+            let texto = edicao.buffer.clone();
+            match app.definir_campo(campo, &texto) {
+                Ok(()) => app.edicao = None,
+                Err(erro) => {
+                    if let Some(edicao) = app.edicao.as_mut() {
+                        edicao.erro = Some(erro);
+                    }
+                    return;
+                }
+            }
+            if !matches!(campo, CampoTexto::FiltroHistorico) {
+                app.erro_config = config::salvar(app, &[campo]).err().map(|e| e.to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
 fn handle_input(app: &mut App, key_code: KeyCode) -> io::Result<()> {
+    if app.edicao.is_some() {
+        handle_edicao(app, key_code);
+        return Ok(());
+    }
+
     match app.current_screen {
         Screen::Menu => match key_code {
             KeyCode::Char('q') => {
                 app.should_quit = true;
             }
             KeyCode::Tab => {
-                app.selected_algorithm = if app.selected_algorithm == 0 { 1 } else { 0 };
+                app.selected_algorithm = (app.selected_algorithm + 1) % 3;
             }
-            KeyCode::Up => {
-                if app.selected_m > 0 {
-                    app.selected_m -= 1;
-                }
+            KeyCode::Up if app.selected_m > 0 => {
+                app.selected_m -= 1;
             }
-            KeyCode::Down => {
-                if app.selected_m < app.m_values.len() - 1 {
-                    app.selected_m += 1;
-                }
+            KeyCode::Down if app.selected_m < app.m_values.len() - 1 => {
+                app.selected_m += 1;
             }
-            KeyCode::Left => {
-                if app.selected_r > 0 {
-                    app.selected_r -= 1;
-                }
+            KeyCode::Left if !app.n_values.is_empty() => {
+                app.selected_n = app.selected_n.saturating_sub(1);
             }
-            KeyCode::Right => {
-                if app.selected_r < app.r_values.len() - 1 {
-                    app.selected_r += 1;
-                }
+            KeyCode::Right if !app.n_values.is_empty() => {
+                app.selected_n = (app.selected_n + 1).min(app.n_values.len() - 1);
             }
-            KeyCode::Char('w') | KeyCode::Char('W') => {
-                if app.selected_algorithm == 1 && app.selected_perturbacao > 0 {
-                    app.selected_perturbacao -= 1;
-                    app.perturbacao_state.select(Some(app.selected_perturbacao));
-                }
+            KeyCode::Left if app.selected_r > 0 => {
+                app.selected_r -= 1;
             }
-            KeyCode::Char('s') | KeyCode::Char('S') => {
-                if app.selected_algorithm == 1
-                    && app.selected_perturbacao < app.perturbacao_values.len() - 1
-                {
-                    app.selected_perturbacao += 1;
-                    app.perturbacao_state.select(Some(app.selected_perturbacao));
-                }
+            KeyCode::Right if app.selected_r < app.r_values.len() - 1 => {
+                app.selected_r += 1;
             }
-            KeyCode::Char('a') | KeyCode::Char('A') => {
-                if app.selected_algorithm == 1 && app.selected_max_iter > 0 {
-                    app.selected_max_iter -= 1;
-                    app.max_iter_state.select(Some(app.selected_max_iter));
-                }
+            KeyCode::Char('w') | KeyCode::Char('W')
+                if app.selected_algorithm == 1 && app.selected_perturbacao > 0 =>
+            {
+                app.selected_perturbacao -= 1;
+                app.perturbacao_state.select(Some(app.selected_perturbacao));
             }
-            KeyCode::Char('d') | KeyCode::Char('D') => {
+            KeyCode::Char('s') | KeyCode::Char('S')
                 if app.selected_algorithm == 1
-                    && app.selected_max_iter < app.max_iter_values.len() - 1
-                {
-                    app.selected_max_iter += 1;
-                    app.max_iter_state.select(Some(app.selected_max_iter));
+                    && app.selected_perturbacao < app.perturbacao_values.len() - 1 =>
+            {
+                app.selected_perturbacao += 1;
+                app.perturbacao_state.select(Some(app.selected_perturbacao));
+            }
+            KeyCode::Char('a') | KeyCode::Char('A')
+                if app.selected_algorithm != 0 && app.selected_max_iter > 0 =>
+            {
+                app.selected_max_iter -= 1;
+                app.max_iter_state.select(Some(app.selected_max_iter));
+            }
+            KeyCode::Char('d') | KeyCode::Char('D')
+                if app.selected_algorithm != 0
+                    && app.selected_max_iter < app.max_iter_values.len() - 1 =>
+            {
+                app.selected_max_iter += 1;
+                app.max_iter_state.select(Some(app.selected_max_iter));
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') if !app.instancias_arquivo.is_empty() => {
+                app.selected_instancia_arquivo =
+                    (app.selected_instancia_arquivo + 1) % app.instancias_arquivo.len();
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                app.selected_ambiente = (app.selected_ambiente + 1) % app.ambientes.len();
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                app.selected_elegibilidade =
+                    (app.selected_elegibilidade + 1) % app.elegibilidades.len();
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                app.selected_objetivo = (app.selected_objetivo + 1) % app.objetivos.len();
+            }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                app.selected_prazos = (app.selected_prazos + 1) % app.prazos.len();
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => {
+                app.selected_distribuicao =
+                    (app.selected_distribuicao + 1) % app.distribuicoes.len();
+            }
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                app.selected_setup = (app.selected_setup + 1) % app.setups.len();
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                app.salvar_convergencia = !app.salvar_convergencia;
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                app.saida.formato_csv = match app.saida.formato_csv {
+                    FormatoCsv::Atual => FormatoCsv::Legado,
+                    FormatoCsv::Legado => FormatoCsv::Atual,
+                };
+            }
+            KeyCode::Char('j') | KeyCode::Char('J') => {
+                app.saida.jsonl = !app.saida.jsonl;
+            }
+            KeyCode::Char('b') | KeyCode::Char('B') => {
+                app.saida.sqlite = !app.saida.sqlite;
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => abrir_edicao(app, CampoTexto::Diretorio),
+            KeyCode::Char('f') | KeyCode::Char('F') => abrir_edicao(app, CampoTexto::Arquivo),
+            KeyCode::Char('m') | KeyCode::Char('M') => abrir_edicao(app, CampoTexto::Maquinas),
+            KeyCode::Char('r') | KeyCode::Char('R') => abrir_edicao(app, CampoTexto::Replicacoes),
+            KeyCode::Char('n') | KeyCode::Char('N') => abrir_edicao(app, CampoTexto::Tarefas),
+            KeyCode::Char('x') | KeyCode::Char('X') if app.selected_algorithm == 1 => {
+                abrir_edicao(app, CampoTexto::Perturbacoes)
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') if app.selected_algorithm == 1 => {
+                let i = Adaptacao::TODAS.iter().position(|&a| a == app.adaptacao);
+                app.adaptacao = Adaptacao::TODAS[(i.unwrap_or(0) + 1) % Adaptacao::TODAS.len()];
+                app.erro_config = config::salvar(app, &[CampoTexto::Adaptacao])
+                    .err()
+                    .map(|e| e.to_string());
+            }
+            KeyCode::Char('z') | KeyCode::Char('Z') if app.selected_algorithm != 0 => {
+                abrir_edicao(app, CampoTexto::MaxIteracoes)
+            }
+            KeyCode::Char('k') | KeyCode::Char('K') => abrir_edicao(app, CampoTexto::Execucoes),
+            KeyCode::Char('h') | KeyCode::Char('H') => abrir_historico(app),
+            KeyCode::Char('+') => {
+                let configuracao = app.configuracao();
+                if !app.configuracoes_comparadas.contains(&configuracao) {
+                    app.configuracoes_comparadas.push(configuracao);
                 }
             }
-            KeyCode::Enter => {
-                app.current_screen = Screen::Running;
-                app.results.clear();
-                app.current_exec = 0;
-                app.scroll_position = 0;
+            KeyCode::Char('-') => {
+                app.configuracoes_comparadas.pop();
+            }
+            KeyCode::Char('=') if app.configuracoes_comparadas.len() >= 2 => {
+                iniciar_execucao(app);
+                app.comparacao = Some(Comparacao::new(app.configuracoes_comparadas.clone()));
             }
+            KeyCode::Enter => iniciar_execucao(app),
             _ => {}
         },
         Screen::Running => {
-            if let KeyCode::Char('q') = key_code {
+            if let KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc = key_code {
                 app.current_screen = Screen::Menu;
             }
         }
@@ -517,10 +2116,130 @@ fn handle_input(app: &mut App, key_code: KeyCode) -> io::Result<()> {
                 app.current_screen = Screen::Menu;
                 app.scroll_position = 0;
             }
-            KeyCode::Up => {
-                if app.scroll_position > 0 {
-                    app.scroll_position -= 1;
+            KeyCode::Up if app.scroll_position > 0 => {
+                app.scroll_position -= 1;
+            }
+            KeyCode::Down => {
+                app.scroll_position += 1;
+            }
+            KeyCode::Char('g') | KeyCode::Char('G') if !app.results.is_empty() => {
+                app.current_screen = Screen::Gantt;
+                app.gantt_replicacao = 0;
+                app.gantt_ponto = None;
+                app.scroll_position = 0;
+            }
+            KeyCode::Char('p') | KeyCode::Char('P')
+                if app.results.iter().any(|r| !r.frente.is_empty()) =>
+            {
+                app.current_screen = Screen::Frente;
+                app.gantt_replicacao = 0;
+                app.frente_ponto = 0;
+            }
+            _ => {}
+        },
+        Screen::Historico => {
+            let Some(historico) = app.historico.as_mut() else {
+                app.current_screen = Screen::Menu;
+                return Ok(());
+            };
+            match key_code {
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                    app.current_screen = Screen::Menu;
+                }
+                KeyCode::Up => historico.mover(-1),
+                KeyCode::Down => historico.mover(1),
+                KeyCode::PageUp => historico.mover(-10),
+                KeyCode::PageDown => historico.mover(10),
+                KeyCode::Char('o') | KeyCode::Char('O') => historico.proxima_ordem(),
+                KeyCode::Char('i') | KeyCode::Char('I') => historico.inverter_ordem(),
+                KeyCode::Char(' ') => historico.alternar_selecao(),
+                KeyCode::Char('/') | KeyCode::Char('f') | KeyCode::Char('F') => {
+                    abrir_edicao(app, CampoTexto::FiltroHistorico)
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') => abrir_historico(app),
+                KeyCode::Enter if historico.atual().is_some() => {
+                    app.current_screen = Screen::HistoricoDetalhe;
+                }
+                KeyCode::Char('c') | KeyCode::Char('C') if historico.selecionados.len() >= 2 => {
+                    app.current_screen = Screen::HistoricoComparacao;
                 }
+                _ => {}
+            }
+        }
+        Screen::Comparacao => match key_code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc | KeyCode::Enter => {
+                app.current_screen = Screen::Menu;
+                app.scroll_position = 0;
+            }
+            KeyCode::Up if app.scroll_position > 0 => {
+                app.scroll_position -= 1;
+            }
+            KeyCode::Down
+                if app
+                    .comparacao
+                    .as_ref()
+                    .is_some_and(|c| (app.scroll_position as usize) + 1 < c.valores.len()) =>
+            {
+                app.scroll_position += 1;
+            }
+            _ => {}
+        },
+        Screen::HistoricoDetalhe | Screen::HistoricoComparacao => match key_code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc | KeyCode::Enter => {
+                app.current_screen = Screen::Historico;
+            }
+            _ => {}
+        },
+        Screen::Frente => match key_code {
+            KeyCode::Char('q') | KeyCode::Char('p') | KeyCode::Char('P') | KeyCode::Esc => {
+                app.current_screen = Screen::Results;
+                app.scroll_position = 0;
+            }
+            KeyCode::Left if app.gantt_replicacao > 0 => {
+                app.gantt_replicacao -= 1;
+                app.frente_ponto = 0;
+            }
+            KeyCode::Right if app.gantt_replicacao + 1 < app.results.len() => {
+                app.gantt_replicacao += 1;
+                app.frente_ponto = 0;
+            }
+            KeyCode::Up if app.frente_ponto > 0 => {
+                app.frente_ponto -= 1;
+            }
+            KeyCode::Down
+                if app
+                    .results
+                    .get(app.gantt_replicacao)
+                    .is_some_and(|r| app.frente_ponto + 1 < r.frente.len()) =>
+            {
+                app.frente_ponto += 1;
+            }
+            KeyCode::Char('g') | KeyCode::Char('G') => {
+                app.current_screen = Screen::Gantt;
+                app.gantt_ponto = Some(app.frente_ponto);
+                app.scroll_position = 0;
+            }
+            _ => {}
+        },
+        Screen::Gantt => match key_code {
+            KeyCode::Char('q') | KeyCode::Char('g') | KeyCode::Char('G') | KeyCode::Esc => {
+                app.current_screen = if app.gantt_ponto.take().is_some() {
+                    Screen::Frente
+                } else {
+                    Screen::Results
+                };
+                app.scroll_position = 0;
+            }
+            KeyCode::Left if app.gantt_replicacao > 0 && app.gantt_ponto.is_none() => {
+                app.gantt_replicacao -= 1;
+            }
+            KeyCode::Right
+                if app.gantt_replicacao + 1 < app.results.len() && app.gantt_ponto.is_none() =>
+            {
+                app.gantt_replicacao += 1;
+            }
+            KeyCode::Up if app.scroll_position > 0 => {
+                app.scroll_position -= 1;
             }
             KeyCode::Down => {
                 app.scroll_position += 1;
@@ -531,28 +2250,191 @@ fn handle_input(app: &mut App, key_code: KeyCode) -> io::Result<()> {
     Ok(())
 }
 
-fn execute_blm(app: &mut App) {
-    let m = app.m_values[app.selected_m];
-    let r = app.r_values[app.selected_r];
-    let n = (m as f64).powf(r) as usize;
+/// Instância de uma replicação: a lida de arquivo, ou uma gerada com a semente,
+/// com as restrições escolhidas no menu aplicadas.
+fn montar_instancia(app: &App, n: usize, m: usize, semente: u64) -> io::Result<Instancia> {
+    let instancia = match app.instancia_arquivo() {
+        Some((_, instancia)) => instancia.clone(),
+        None => Instancia::gerar(
+            n,
+            m,
+            &app.ambientes[app.selected_ambiente],
+            &app.distribuicoes[app.selected_distribuicao],
+            semente,
+        ),
+    };
+    instancia
+        .com_setup(&app.setups[app.selected_setup], semente)
+        .com_prazos(&app.prazos[app.selected_prazos], semente)
+        .restringir(&app.elegibilidades[app.selected_elegibilidade], semente)
+        .and_then(|instancia| match &app.precedencias {
+            Some((_, arcos)) => instancia.com_precedencias(arcos),
+            None => Ok(instancia),
+        })
+        .and_then(|instancia| match &app.reprogramacao {
+            Some(reprogramacao) => instancia.com_reprogramacao(reprogramacao),
+            None => Ok(instancia),
+        })
+}
 
-    let result = if app.selected_algorithm == 0 {
-        melhor_melhora(m, n, r)
-    } else {
-        let perturbacao = app.perturbacao_values[app.selected_perturbacao];
-        let max_iter = app.max_iter_values[app.selected_max_iter];
-        busca_local_iterada(m, n, r, perturbacao, max_iter)
+/// Executa a configuração sobre a instância, redesenhando a tela de progresso
+/// durante a busca, limitado a ~20 quadros por segundo. Devolve `None` se o
+/// usuário cancelou a execução com Q ou ESC.
+fn executar_configuracao<B: Backend>(
+    app: &mut App,
+    terminal: &mut Terminal<B>,
+    instancia: &Instancia,
+    configuracao: Configuracao,
+    r: Option<f64>,
+    semente: u64,
+) -> Option<Result> {
+    let objetivo = app.objetivo();
+    app.convergencia_atual.clear();
+    let mut ultimo_desenho = Instant::now();
+    let mut cancelada = false;
+    let mut observador = |ponto: &PontoConvergencia| {
+        app.convergencia_atual.push(*ponto);
+        if ultimo_desenho.elapsed() >= Duration::from_millis(50) {
+            let _ = terminal.draw(|f| desenhar(f, app));
+            ultimo_desenho = Instant::now();
+            cancelada = cancelamento_pedido();
+        }
+        if cancelada {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    };
+
+    let result = configuracao.executar(instancia, objetivo, r, semente, &mut observador);
+    (!cancelada).then_some(result)
+}
+
+/// Consome as teclas pendentes, indicando se alguma delas pede o cancelamento.
+fn cancelamento_pedido() -> bool {
+    let mut pedido = false;
+    while event::poll(Duration::ZERO).unwrap_or(false) {
+        if let Ok(Event::Key(key)) = event::read() {
+            pedido |= matches!(
+                key.code,
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc
+            );
+        }
+    }
+    pedido
+}
+
+/// Confere a alocação (e a frente, se houver) e grava o resultado nas saídas
+/// configuradas. Uma alocação inválida interrompe a execução.
+fn registrar_resultado(app: &mut App, instancia: &Instancia, result: &Result) -> bool {
+    let invalida = std::iter::once(&result.alocacao)
+        .chain(result.frente.iter().map(|p| &p.alocacao))
+        .find_map(|alocacao| instancia.verificar(alocacao).err());
+    if let Some(e) = invalida {
+        app.erro_execucao = Some(format!("alocação inválida: {e}"));
+        app.current_screen = Screen::Results;
+        return false;
+    }
+
+    // Com a tela alternativa ativa, falhas ficam para a tela de resultados
+    if let Err(e) = salvar_resultado(result, &app.saida) {
+        app.erro_salvar = Some(format!("{}: {e}", app.saida.descrever()));
+    }
+    if app.salvar_convergencia {
+        let caminho = app.saida.caminho(&app.convergencia_filename);
+        if let Err(e) = salvar_convergencia_csv(result, app.current_exec + 1, &caminho) {
+            app.erro_salvar = Some(format!("convergência em {}: {e}", caminho.display()));
+        }
+    }
+    if !result.frente.is_empty() {
+        let caminho = app.saida.caminho(&app.frente_filename);
+        if let Err(e) = salvar_frente_csv(result, app.current_exec + 1, &caminho) {
+            app.erro_salvar = Some(format!("frente de Pareto em {}: {e}", caminho.display()));
+        }
+    }
+    true
+}
+
+fn execute_blm<B: Backend>(app: &mut App, terminal: &mut Terminal<B>) {
+    let inicio = Instant::now();
+    let m = app.m_values[app.selected_m];
+    let (n, r) = app.tamanho();
+    let r = r.filter(|_| app.instancia_arquivo().is_none());
+    let semente: u64 = rand::random();
+    let instancia = match montar_instancia(app, n, m, semente) {
+        Ok(instancia) => instancia,
+        Err(e) => {
+            app.erro_execucao = Some(e.to_string());
+            app.current_screen = Screen::Results;
+            return;
+        }
     };
 
-    // Save to CSV
-    if let Err(e) = salvar_csv(&result, &app.output_filename) {
-        eprintln!("Erro ao salvar arquivo: {e}");
+    let configuracao = app.configuracao();
+    let Some(result) = executar_configuracao(app, terminal, &instancia, configuracao, r, semente)
+    else {
+        app.current_screen = Screen::Menu;
+        return;
+    };
+    if !registrar_resultado(app, &instancia, &result) {
+        return;
     }
 
     app.results.push(result);
     app.current_exec += 1;
+    app.tempo_execucoes += inicio.elapsed();
 
-    if app.current_exec >= 10 {
+    if app.current_exec >= app.execucoes {
         app.current_screen = Screen::Results;
     }
 }
+
+/// Uma replicação da comparação: todas as configurações sobre a mesma instância e semente.
+fn execute_comparacao<B: Backend>(app: &mut App, terminal: &mut Terminal<B>) {
+    let inicio = Instant::now();
+    let m = app.m_values[app.selected_m];
+    let (n, r) = app.tamanho();
+    let r = r.filter(|_| app.instancia_arquivo().is_none());
+    let semente: u64 = rand::random();
+    let instancia = match montar_instancia(app, n, m, semente) {
+        Ok(instancia) => instancia,
+        Err(e) => {
+            app.erro_execucao = Some(e.to_string());
+            app.current_screen = Screen::Results;
+            return;
+        }
+    };
+
+    let configuracoes = match &app.comparacao {
+        Some(comparacao) => comparacao.configuracoes.clone(),
+        None => return,
+    };
+    let mut valores = Vec::new();
+    let mut tempos = Vec::new();
+    for (k, configuracao) in configuracoes.into_iter().enumerate() {
+        app.configuracao_atual = k;
+        let Some(result) =
+            executar_configuracao(app, terminal, &instancia, configuracao, r, semente)
+        else {
+            app.current_screen = Screen::Menu;
+            return;
+        };
+        if !registrar_resultado(app, &instancia, &result) {
+            return;
+        }
+        valores.push(result.valor_final);
+        tempos.push(result.tempo_exec);
+    }
+    if let Some(comparacao) = app.comparacao.as_mut() {
+        comparacao.sementes.push(semente);
+        comparacao.valores.push(valores);
+        comparacao.tempos.push(tempos);
+    }
+
+    app.current_exec += 1;
+    app.tempo_execucoes += inicio.elapsed();
+
+    if app.current_exec >= app.execucoes {
+        app.current_screen = Screen::Comparacao;
+    }
+}
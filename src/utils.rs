@@ -1,57 +1,926 @@
-use std::fs::OpenOptions;
-use std::io::{self, Write};
+use crate::estatistica::desvio_melhor_conhecido;
+use crate::instancia::Referencia;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy)]
+pub struct PontoConvergencia {
+    pub iteracao: usize,
+    pub tempo_ms: f64,
+    /// Valor do objetivo na solução corrente e na melhor encontrada.
+    pub valor_atual: f64,
+    pub valor_melhor: f64,
+    /// Fração de tarefas perturbada para chegar a este ponto; 0 fora da busca local iterada.
+    pub perturbacao: f64,
+}
+
+#[derive(Clone, Copy)]
+pub struct TarefaAlocada {
+    pub tarefa: usize,
+    pub inicio: f64,
+    pub fim: f64,
+}
+
+/// Solução não dominada do modo multiobjetivo.
+#[derive(Clone)]
+pub struct PontoPareto {
+    pub makespan: f64,
+    pub variancia: f64,
+    /// Tarefas em máquina diferente da escala de referência.
+    pub migracoes: usize,
+    pub alocacao: Vec<Vec<TarefaAlocada>>,
+}
 
 pub struct Result {
     pub n_tarefas: usize,
     pub n_maquinas: usize,
-    pub replicacao: f64,
+    /// Expoente r de n = m^r; `None` quando n foi informado diretamente.
+    pub replicacao: Option<f64>,
     pub tempo_exec: f64,
     pub iteracoes: usize,
-    pub makespan_inicial: u32,
-    pub makespan_final: u32,
+    /// Valor do objetivo otimizado na solução inicial e na final.
+    pub valor_inicial: f64,
+    pub valor_final: f64,
+    pub objetivo: String,
     pub algoritmo: String,
     pub perturbacao: f64,
+    pub max_iteracoes: u32,
+    pub criterio_parada: String,
+    pub semente: u64,
+    pub ambiente: String,
+    pub limite_inferior: f64,
+    /// Nome e melhor conhecido da instância de benchmark, se lida de arquivo.
+    pub referencia: Option<Referencia>,
+    /// Tarefas fora da máquina da escala de referência; `None` quando não há uma.
+    pub migracoes: Option<usize>,
+    pub alocacao: Vec<Vec<TarefaAlocada>>,
+    pub convergencia: Vec<PontoConvergencia>,
+    /// Frente de Pareto, ordenada por makespan; vazia nos modos de um objetivo.
+    pub frente: Vec<PontoPareto>,
+    /// Hipervolume da frente, normalizado pelos limites fixos da instância.
+    pub hipervolume: Option<f64>,
+}
+
+/// Valores inteiros (máquinas idênticas) saem sem casas decimais; os demais com duas.
+pub fn formatar_valor(valor: f64) -> String {
+    if valor.fract() == 0.0 {
+        format!("{valor:.0}")
+    } else {
+        format!("{valor:.2}")
+    }
+}
+
+/// Lê uma lista de números separados por vírgula, em que cada item é um
+/// valor ou uma faixa "a..b" (passo 1) ou "a..b:passo", com b incluído.
+/// Devolve `None` se algum item for inválido ou a lista passar de 1000 valores.
+pub fn ler_lista(texto: &str) -> Option<Vec<f64>> {
+    const MAXIMO: usize = 1000;
+    let mut valores = Vec::new();
+    for item in texto.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        let Some((inicio, resto)) = item.split_once("..") else {
+            valores.push(item.parse().ok().filter(|v: &f64| v.is_finite())?);
+            continue;
+        };
+        let (fim, passo) = match resto.split_once(':') {
+            Some((fim, passo)) => (fim, passo.trim().parse().ok()?),
+            None => (resto, 1.0),
+        };
+        let inicio: f64 = inicio.trim().parse().ok()?;
+        let fim: f64 = fim.trim().parse().ok()?;
+        if !(passo > 0.0 && inicio <= fim && (fim - inicio) / passo < MAXIMO as f64) {
+            return None;
+        }
+        let mut k = 0.0;
+        while inicio + k * passo <= fim + 1e-9 {
+            // Arredonda o erro acumulado de faixas como 0.1..0.9:0.1
+            valores.push(((inicio + k * passo) * 1e9).round() / 1e9);
+            k += 1.0;
+        }
+    }
+    (!valores.is_empty() && valores.len() <= MAXIMO).then_some(valores)
+}
+
+/// Junta os valores com vírgulas, no formato lido por [`ler_lista`].
+pub fn juntar<T: ToString>(valores: &[T]) -> String {
+    let textos: Vec<String> = valores.iter().map(T::to_string).collect();
+    textos.join(",")
+}
+
+/// Cabeçalhos conhecidos do CSV de resultados, da versão mais antiga para a atual.
+pub const ESQUEMAS_CSV: [(u32, &str); 6] = [
+    (1, "heuristica,n,m,replicacao,tempo(ms),iteracoes,valor,parametro"),
+    (
+        2,
+        "heuristica,n,m,replicacao,tempo(ms),iteracoes,valor_inicial,valor,parametro,max_iter,semente,criterio_parada,timestamp,hostname",
+    ),
+    (
+        3,
+        "heuristica,n,m,replicacao,tempo(ms),iteracoes,valor_inicial,valor,parametro,max_iter,semente,criterio_parada,timestamp,hostname,ambiente",
+    ),
+    (
+        4,
+        "heuristica,n,m,replicacao,tempo(ms),iteracoes,valor_inicial,valor,parametro,max_iter,semente,criterio_parada,timestamp,hostname,ambiente,objetivo",
+    ),
+    (
+        5,
+        "heuristica,n,m,replicacao,tempo(ms),iteracoes,valor_inicial,valor,parametro,max_iter,semente,criterio_parada,timestamp,hostname,ambiente,objetivo,migracoes",
+    ),
+    (
+        6,
+        "heuristica,n,m,replicacao,tempo(ms),iteracoes,valor_inicial,valor,parametro,max_iter,semente,criterio_parada,timestamp,hostname,ambiente,objetivo,migracoes,instancia,melhor_conhecido,desvio_melhor",
+    ),
+];
+
+/// Tipo SQLite de cada coluna do esquema atual (com `tempo(ms)` como
+/// `tempo_ms`), mais a versão do esquema; a tabela é criada com estes tipos,
+/// sem depender dos valores da primeira linha gravada.
+const COLUNAS_SQLITE: [(&str, &str); 21] = [
+    ("versao", "INTEGER"),
+    ("heuristica", "TEXT"),
+    ("n", "INTEGER"),
+    ("m", "INTEGER"),
+    ("replicacao", "REAL"),
+    ("tempo_ms", "REAL"),
+    ("iteracoes", "INTEGER"),
+    ("valor_inicial", "REAL"),
+    ("valor", "REAL"),
+    ("parametro", "REAL"),
+    ("max_iter", "INTEGER"),
+    ("semente", "TEXT"),
+    ("criterio_parada", "TEXT"),
+    ("timestamp", "TEXT"),
+    ("hostname", "TEXT"),
+    ("ambiente", "TEXT"),
+    ("objetivo", "TEXT"),
+    ("migracoes", "INTEGER"),
+    ("instancia", "TEXT"),
+    ("melhor_conhecido", "REAL"),
+    ("desvio_melhor", "REAL"),
+];
+
+/// Valor das colunas novas em linhas migradas, quando não é simplesmente NA.
+const COLUNAS_PADRAO: [(&str, &str); 1] = [("objetivo", "makespan")];
+
+pub const VERSAO_CSV_ATUAL: u32 = 6;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FormatoCsv {
+    Atual,
+    Legado,
+}
+
+impl FormatoCsv {
+    pub fn versao(self) -> u32 {
+        match self {
+            FormatoCsv::Atual => VERSAO_CSV_ATUAL,
+            FormatoCsv::Legado => 1,
+        }
+    }
+
+    pub fn nome(self) -> &'static str {
+        match self {
+            FormatoCsv::Atual => "atual",
+            FormatoCsv::Legado => "legado (v1)",
+        }
+    }
+}
+
+fn cabecalho_csv(versao: u32) -> &'static str {
+    ESQUEMAS_CSV
+        .iter()
+        .find(|(v, _)| *v == versao)
+        .map(|(_, c)| *c)
+        .unwrap_or(ESQUEMAS_CSV[ESQUEMAS_CSV.len() - 1].1)
+}
+
+fn versao_do_cabecalho(cabecalho: &str) -> Option<u32> {
+    ESQUEMAS_CSV
+        .iter()
+        .find(|(_, c)| *c == cabecalho)
+        .map(|(v, _)| *v)
+}
+
+/// Data e hora UTC atuais no formato ISO 8601 (ex.: 2024-05-01T13:45:00Z).
+pub fn timestamp_iso8601() -> String {
+    let segundos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (dias, resto) = (segundos / 86_400, segundos % 86_400);
+
+    // Conversão de dias desde 1970-01-01 para data civil (algoritmo de H. Hinnant)
+    let z = dias as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let dia = doy - (153 * mp + 2) / 5 + 1;
+    let mes = if mp < 10 { mp + 3 } else { mp - 9 };
+    let ano = yoe + era * 400 + i64::from(mes <= 2);
+
+    format!(
+        "{ano:04}-{mes:02}-{dia:02}T{:02}:{:02}:{:02}Z",
+        resto / 3600,
+        (resto % 3600) / 60,
+        resto % 60
+    )
+}
+
+pub fn hostname() -> String {
+    let nome = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .or_else(|_| std::fs::read_to_string("/etc/hostname"))
+        .unwrap_or_default();
+    let nome = nome.trim().replace(',', "_");
+    if nome.is_empty() {
+        "desconhecido".to_string()
+    } else {
+        nome
+    }
+}
+
+fn campos_csv(resultado: &Result, versao: u32) -> Vec<String> {
+    let parametro = if resultado.perturbacao > 0.0 {
+        resultado.perturbacao.to_string()
+    } else {
+        "NA".to_string()
+    };
+
+    let mut campos = vec![
+        resultado.algoritmo.clone(),
+        resultado.n_tarefas.to_string(),
+        resultado.n_maquinas.to_string(),
+        resultado
+            .replicacao
+            .map_or("NA".to_string(), |r| r.to_string()),
+        format!("{:.2}", resultado.tempo_exec),
+        resultado.iteracoes.to_string(),
+    ];
+    if versao == 1 {
+        campos.extend([formatar_valor(resultado.valor_final), parametro]);
+        return campos;
+    }
+
+    let max_iter = if resultado.max_iteracoes > 0 {
+        resultado.max_iteracoes.to_string()
+    } else {
+        "NA".to_string()
+    };
+    campos.extend([
+        formatar_valor(resultado.valor_inicial),
+        formatar_valor(resultado.valor_final),
+        parametro,
+        max_iter,
+        resultado.semente.to_string(),
+        resultado.criterio_parada.clone(),
+        timestamp_iso8601(),
+        hostname(),
+    ]);
+    if versao >= 3 {
+        campos.push(resultado.ambiente.clone());
+    }
+    if versao >= 4 {
+        campos.push(resultado.objetivo.clone());
+    }
+    if versao >= 5 {
+        campos.push(
+            resultado
+                .migracoes
+                .map_or("NA".to_string(), |k| k.to_string()),
+        );
+    }
+    if versao >= 6 {
+        let referencia = resultado.referencia.as_ref();
+        campos.extend([
+            referencia.map_or("NA".to_string(), |r| r.nome.clone()),
+            referencia
+                .and_then(|r| r.melhor_conhecido)
+                .map_or("NA".to_string(), formatar_valor),
+            desvio_melhor_conhecido(resultado).map_or("NA".to_string(), |d| format!("{d:.4}")),
+        ]);
+    }
+    campos
 }
 
-pub fn salvar_csv(resultado: &Result, filename: &str) -> io::Result<()> {
-    // Create results directory if it doesn't exist
-    std::fs::create_dir_all("results")?;
+/// Reescreve um CSV de versão antiga no esquema `versao`, preenchendo com NA
+/// (ou o valor de `COLUNAS_PADRAO`) as colunas que não existiam. O original é preservado em `<arquivo>.v<N>.bak`.
+pub fn migrar_csv(filepath: &str, versao: u32) -> io::Result<()> {
+    let mut leitor = csv::Reader::from_path(filepath)?;
+    let antigo = leitor.headers()?.clone();
+    let versao_antiga = versao_do_cabecalho(&antigo.iter().collect::<Vec<_>>().join(","))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{filepath}: cabeçalho desconhecido, migração recusada"),
+            )
+        })?;
+    let registros: Vec<csv::StringRecord> = leitor.records().collect::<csv::Result<_>>()?;
 
-    // Prepend results/ to the filename
-    let filepath = format!("results/{filename}");
+    let novo: Vec<&str> = cabecalho_csv(versao).split(',').collect();
+    let mapa: Vec<Option<usize>> = novo
+        .iter()
+        .map(|c| antigo.iter().position(|a| a == *c))
+        .collect();
+
+    std::fs::copy(filepath, format!("{filepath}.v{versao_antiga}.bak"))?;
+    let mut escritor = csv::Writer::from_path(filepath)?;
+    escritor.write_record(&novo)?;
+    let padroes: Vec<&str> = novo
+        .iter()
+        .map(|c| {
+            COLUNAS_PADRAO
+                .iter()
+                .find(|(coluna, _)| coluna == c)
+                .map_or("NA", |(_, padrao)| *padrao)
+        })
+        .collect();
+    for registro in &registros {
+        let linha: Vec<&str> = mapa
+            .iter()
+            .zip(&padroes)
+            .map(|(idx, padrao)| idx.and_then(|i| registro.get(i)).unwrap_or(padrao))
+            .collect();
+        escritor.write_record(&linha)?;
+    }
+    escritor.flush()?;
+    Ok(())
+}
+
+pub fn salvar_csv(resultado: &Result, filepath: &Path, formato: FormatoCsv) -> io::Result<()> {
+    let filepath = filepath.to_string_lossy().to_string();
+    let versao = formato.versao();
 
     let file_exists = std::path::Path::new(&filepath).exists();
-    let mut file = OpenOptions::new()
+    if file_exists {
+        // Nunca anexar linhas de um esquema a um arquivo de outro esquema;
+        // só o cabeçalho é lido, não o arquivo inteiro
+        let primeira = BufReader::new(File::open(&filepath)?)
+            .lines()
+            .next()
+            .transpose()?
+            .unwrap_or_default();
+        let cabecalho = primeira.trim_end();
+        match versao_do_cabecalho(cabecalho) {
+            Some(v) if v == versao => {}
+            Some(v) if v < versao => migrar_csv(&filepath, versao)?,
+            Some(v) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{filepath} usa o esquema v{v}, mais novo que o v{versao} solicitado"),
+                ))
+            }
+            None if cabecalho.is_empty() => {}
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{filepath} tem um cabeçalho desconhecido"),
+                ))
+            }
+        }
+    }
+
+    let vazio = std::fs::metadata(&filepath).map_or(true, |m| m.len() == 0);
+    let file = OpenOptions::new()
         .create(true)
         .append(true)
         .open(&filepath)?;
+    // Campos com vírgula ou aspas (instância, ambiente, hostname) saem entre aspas
+    let mut escritor = csv::Writer::from_writer(file);
 
     // Write header if file is new
+    if vazio {
+        escritor.write_record(cabecalho_csv(versao).split(','))?;
+    }
+
+    escritor.write_record(campos_csv(resultado, versao))?;
+    escritor.flush()?;
+
+    Ok(())
+}
+
+pub fn salvar_convergencia_csv(
+    resultado: &Result,
+    execucao: usize,
+    filepath: &Path,
+) -> io::Result<()> {
+    let file_exists = filepath.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filepath)?;
+
     if !file_exists {
         writeln!(
             file,
-            "heuristica,n,m,replicacao,tempo(ms),iteracoes,valor,parametro"
+            "heuristica,n,m,execucao,iteracao,tempo(ms),valor_atual,valor_melhor,perturbacao"
         )?;
     }
 
-    // Write data: heuristica,n,m,replicacao,tempo(ms),iteracoes,valor,parametro
-    let parametro = if resultado.perturbacao > 0.0 {
-        format!("{:.1}", resultado.perturbacao)
-    } else {
-        "NA".to_string()
-    };
-    writeln!(
-        file,
-        "{},{},{},{},{:.2},{},{},{}",
-        resultado.algoritmo,
-        resultado.n_tarefas,
-        resultado.n_maquinas,
-        resultado.replicacao,
-        resultado.tempo_exec,
-        resultado.iteracoes,
-        resultado.makespan_final,
-        parametro
-    )?;
+    for ponto in &resultado.convergencia {
+        writeln!(
+            file,
+            "{},{},{},{},{},{:.2},{},{},{}",
+            resultado.algoritmo,
+            resultado.n_tarefas,
+            resultado.n_maquinas,
+            execucao,
+            ponto.iteracao,
+            ponto.tempo_ms,
+            formatar_valor(ponto.valor_atual),
+            formatar_valor(ponto.valor_melhor),
+            if ponto.perturbacao > 0.0 {
+                format!("{:.3}", ponto.perturbacao)
+            } else {
+                "NA".to_string()
+            }
+        )?;
+    }
 
     Ok(())
 }
+
+/// Acrescenta a frente de Pareto de uma execução ao CSV de frentes, com o
+/// hipervolume da frente repetido em cada ponto.
+pub fn salvar_frente_csv(resultado: &Result, execucao: usize, filepath: &Path) -> io::Result<()> {
+    let file_exists = filepath.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filepath)?;
+
+    if !file_exists {
+        writeln!(
+            file,
+            "heuristica,n,m,execucao,semente,ponto,makespan,variancia,migracoes,hipervolume"
+        )?;
+    }
+
+    for (i, ponto) in resultado.frente.iter().enumerate() {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{},{:.4},{},{:.6}",
+            resultado.algoritmo,
+            resultado.n_tarefas,
+            resultado.n_maquinas,
+            execucao,
+            resultado.semente,
+            i + 1,
+            formatar_valor(ponto.makespan),
+            ponto.variancia,
+            ponto.migracoes,
+            resultado.hipervolume.unwrap_or(0.0)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Onde e em quais formatos os resultados são gravados. O CSV é sempre
+/// escrito; JSON Lines e SQLite usam o mesmo nome base com outra extensão.
+pub struct ConfigSaida {
+    pub diretorio: String,
+    pub arquivo: String,
+    pub formato_csv: FormatoCsv,
+    pub jsonl: bool,
+    pub sqlite: bool,
+}
+
+impl ConfigSaida {
+    pub fn new() -> Self {
+        ConfigSaida {
+            diretorio: "results".to_string(),
+            arquivo: "resultados.csv".to_string(),
+            formato_csv: FormatoCsv::Atual,
+            jsonl: false,
+            sqlite: false,
+        }
+    }
+
+    pub fn caminho(&self, arquivo: &str) -> PathBuf {
+        Path::new(&self.diretorio).join(arquivo)
+    }
+
+    fn caminho_com_extensao(&self, extensao: &str) -> PathBuf {
+        self.caminho(&self.arquivo).with_extension(extensao)
+    }
+
+    pub fn descrever(&self) -> String {
+        let mut descricao = self.caminho(&self.arquivo).display().to_string();
+        if self.jsonl {
+            descricao.push_str(" + .jsonl");
+        }
+        if self.sqlite {
+            descricao.push_str(" + .sqlite");
+        }
+        descricao
+    }
+}
+
+enum Campo {
+    Texto(String),
+    Inteiro(i64),
+    Real(f64),
+    Nulo,
+}
+
+/// Metadados de uma execução como pares (coluna, valor), comuns ao JSON Lines e ao SQLite.
+fn registro(resultado: &Result) -> Vec<(&'static str, Campo)> {
+    let opcional_real = |v: f64| if v > 0.0 { Campo::Real(v) } else { Campo::Nulo };
+    vec![
+        ("versao", Campo::Inteiro(VERSAO_CSV_ATUAL as i64)),
+        ("heuristica", Campo::Texto(resultado.algoritmo.clone())),
+        ("n", Campo::Inteiro(resultado.n_tarefas as i64)),
+        ("m", Campo::Inteiro(resultado.n_maquinas as i64)),
+        (
+            "replicacao",
+            resultado.replicacao.map_or(Campo::Nulo, Campo::Real),
+        ),
+        ("tempo_ms", Campo::Real(resultado.tempo_exec)),
+        ("iteracoes", Campo::Inteiro(resultado.iteracoes as i64)),
+        ("valor_inicial", Campo::Real(resultado.valor_inicial)),
+        ("valor", Campo::Real(resultado.valor_final)),
+        ("parametro", opcional_real(resultado.perturbacao)),
+        (
+            "max_iter",
+            if resultado.max_iteracoes > 0 {
+                Campo::Inteiro(resultado.max_iteracoes as i64)
+            } else {
+                Campo::Nulo
+            },
+        ),
+        // Texto para não perder precisão de sementes acima de 2^53 / i64::MAX
+        ("semente", Campo::Texto(resultado.semente.to_string())),
+        (
+            "criterio_parada",
+            Campo::Texto(resultado.criterio_parada.clone()),
+        ),
+        ("timestamp", Campo::Texto(timestamp_iso8601())),
+        ("hostname", Campo::Texto(hostname())),
+        ("ambiente", Campo::Texto(resultado.ambiente.clone())),
+        ("objetivo", Campo::Texto(resultado.objetivo.clone())),
+        (
+            "migracoes",
+            resultado
+                .migracoes
+                .map_or(Campo::Nulo, |k| Campo::Inteiro(k as i64)),
+        ),
+        (
+            "instancia",
+            resultado
+                .referencia
+                .as_ref()
+                .map_or(Campo::Nulo, |r| Campo::Texto(r.nome.clone())),
+        ),
+        (
+            "melhor_conhecido",
+            resultado
+                .referencia
+                .as_ref()
+                .and_then(|r| r.melhor_conhecido)
+                .map_or(Campo::Nulo, Campo::Real),
+        ),
+        (
+            "desvio_melhor",
+            desvio_melhor_conhecido(resultado).map_or(Campo::Nulo, Campo::Real),
+        ),
+    ]
+}
+
+fn escapar_json(texto: &str) -> String {
+    let mut saida = String::with_capacity(texto.len());
+    for c in texto.chars() {
+        match c {
+            '"' => saida.push_str("\\\""),
+            '\\' => saida.push_str("\\\\"),
+            '\n' => saida.push_str("\\n"),
+            '\r' => saida.push_str("\\r"),
+            '\t' => saida.push_str("\\t"),
+            c if (c as u32) < 0x20 => saida.push_str(&format!("\\u{:04x}", c as u32)),
+            c => saida.push(c),
+        }
+    }
+    saida
+}
+
+pub fn salvar_jsonl(resultado: &Result, filepath: &Path) -> io::Result<()> {
+    let campos: Vec<String> = registro(resultado)
+        .into_iter()
+        .map(|(nome, valor)| {
+            let valor = match valor {
+                Campo::Texto(t) => format!("\"{}\"", escapar_json(&t)),
+                Campo::Inteiro(i) => i.to_string(),
+                Campo::Real(r) if r.is_finite() => r.to_string(),
+                Campo::Real(_) | Campo::Nulo => "null".to_string(),
+            };
+            format!("\"{nome}\":{valor}")
+        })
+        .collect();
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filepath)?;
+    writeln!(file, "{{{}}}", campos.join(","))?;
+    Ok(())
+}
+
+pub fn salvar_sqlite(resultado: &Result, filepath: &Path) -> io::Result<()> {
+    let erro_sqlite = |e: rusqlite::Error| io::Error::other(format!("SQLite: {e}"));
+    let conexao = rusqlite::Connection::open(filepath).map_err(erro_sqlite)?;
+    let campos = registro(resultado);
+
+    let colunas: Vec<String> = COLUNAS_SQLITE
+        .iter()
+        .map(|(nome, tipo)| format!("{nome} {tipo}"))
+        .collect();
+    conexao
+        .execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS resultados (id INTEGER PRIMARY KEY AUTOINCREMENT, {})",
+                colunas.join(", ")
+            ),
+            [],
+        )
+        .map_err(erro_sqlite)?;
+
+    // Colunas novas do esquema são acrescentadas a tabelas de versões anteriores
+    let existentes: Vec<String> = conexao
+        .prepare("SELECT name FROM pragma_table_info('resultados')")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |linha| linha.get(0))?
+                .collect::<rusqlite::Result<_>>()
+        })
+        .map_err(erro_sqlite)?;
+    for (nome, tipo) in COLUNAS_SQLITE {
+        if !existentes.iter().any(|c| c == nome) {
+            conexao
+                .execute(
+                    &format!("ALTER TABLE resultados ADD COLUMN {nome} {tipo}"),
+                    [],
+                )
+                .map_err(erro_sqlite)?;
+        }
+    }
+
+    let nomes: Vec<&str> = campos.iter().map(|(nome, _)| *nome).collect();
+    let marcadores: Vec<String> = (1..=campos.len()).map(|i| format!("?{i}")).collect();
+    let valores: Vec<rusqlite::types::Value> = campos
+        .into_iter()
+        .map(|(_, valor)| match valor {
+            Campo::Texto(t) => rusqlite::types::Value::Text(t),
+            Campo::Inteiro(i) => rusqlite::types::Value::Integer(i),
+            Campo::Real(r) => rusqlite::types::Value::Real(r),
+            Campo::Nulo => rusqlite::types::Value::Null,
+        })
+        .collect();
+    conexao
+        .execute(
+            &format!(
+                "INSERT INTO resultados ({}) VALUES ({})",
+                nomes.join(", "),
+                marcadores.join(", ")
+            ),
+            rusqlite::params_from_iter(valores),
+        )
+        .map_err(erro_sqlite)?;
+    Ok(())
+}
+
+/// Grava o resultado em todos os formatos habilitados em `saida`.
+pub fn salvar_resultado(resultado: &Result, saida: &ConfigSaida) -> io::Result<()> {
+    std::fs::create_dir_all(&saida.diretorio)?;
+
+    salvar_csv(resultado, &saida.caminho(&saida.arquivo), saida.formato_csv)?;
+    if saida.jsonl {
+        salvar_jsonl(resultado, &saida.caminho_com_extensao("jsonl"))?;
+    }
+    if saida.sqlite {
+        salvar_sqlite(resultado, &saida.caminho_com_extensao("sqlite"))?;
+    }
+    Ok(())
+}
+
+/// Arquivo temporário dos testes, apagado quando sai de escopo, inclusive
+/// se o teste entrar em pânico.
+#[cfg(test)]
+pub struct ArquivoTemporario(PathBuf);
+
+#[cfg(test)]
+impl ArquivoTemporario {
+    /// Caminho `tsh-<pid>-<nome>` no diretório temporário, ainda sem arquivo.
+    pub fn new(nome: &str) -> Self {
+        let caminho = std::env::temp_dir().join(format!("tsh-{}-{nome}", std::process::id()));
+        let _ = std::fs::remove_file(&caminho);
+        ArquivoTemporario(caminho)
+    }
+
+    /// Como [`ArquivoTemporario::new`], já gravado com `conteudo`.
+    pub fn com(nome: &str, conteudo: &str) -> Self {
+        let arquivo = ArquivoTemporario::new(nome);
+        std::fs::write(&arquivo.0, conteudo).unwrap();
+        arquivo
+    }
+
+    pub fn caminho(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn texto(&self) -> String {
+        self.0.to_string_lossy().into_owned()
+    }
+}
+
+#[cfg(test)]
+impl Drop for ArquivoTemporario {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resultado() -> Result {
+        Result {
+            n_tarefas: 10,
+            n_maquinas: 2,
+            replicacao: None,
+            tempo_exec: 1.5,
+            iteracoes: 3,
+            valor_inicial: 30.0,
+            valor_final: 20.0,
+            objetivo: "makespan".to_string(),
+            algoritmo: "busca-local-iterada".to_string(),
+            perturbacao: 0.05,
+            max_iteracoes: 100,
+            criterio_parada: "max-iter-sem-melhora".to_string(),
+            semente: 42,
+            ambiente: "identicas".to_string(),
+            limite_inferior: 15.0,
+            referencia: None,
+            migracoes: None,
+            alocacao: Vec::new(),
+            convergencia: Vec::new(),
+            frente: Vec::new(),
+            hipervolume: None,
+        }
+    }
+
+    #[test]
+    fn colunas_sqlite_cobrem_o_registro() {
+        let nomes: Vec<&str> = registro(&resultado()).iter().map(|(n, _)| *n).collect();
+        let declarados: Vec<&str> = COLUNAS_SQLITE.iter().map(|(n, _)| *n).collect();
+        assert_eq!(nomes, declarados);
+    }
+
+    #[test]
+    fn sqlite_usa_tipos_declarados() {
+        let arquivo = ArquivoTemporario::new("resultados.sqlite");
+        let caminho = arquivo.caminho();
+        // A primeira linha não tem instância nem migrações
+        salvar_sqlite(&resultado(), caminho).unwrap();
+        let mut com_instancia = resultado();
+        com_instancia.migracoes = Some(4);
+        com_instancia.referencia = Some(Referencia {
+            nome: "inst-1".to_string(),
+            melhor_conhecido: Some(18.0),
+            otimo: false,
+        });
+        salvar_sqlite(&com_instancia, caminho).unwrap();
+
+        let conexao = rusqlite::Connection::open(caminho).unwrap();
+        let tipo = |coluna: &str| -> String {
+            conexao
+                .query_row(
+                    "SELECT type FROM pragma_table_info('resultados') WHERE name = ?1",
+                    [coluna],
+                    |linha| linha.get(0),
+                )
+                .unwrap()
+        };
+        assert_eq!(tipo("instancia"), "TEXT");
+        assert_eq!(tipo("migracoes"), "INTEGER");
+        assert_eq!(tipo("max_iter"), "INTEGER");
+        let (migracoes, max_iter): (String, String) = conexao
+            .query_row(
+                "SELECT typeof(migracoes), typeof(max_iter) FROM resultados WHERE id = 2",
+                [],
+                |linha| Ok((linha.get(0)?, linha.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (migracoes.as_str(), max_iter.as_str()),
+            ("integer", "integer")
+        );
+        drop(conexao);
+    }
+
+    #[test]
+    fn ler_lista_valores_e_faixas() {
+        assert_eq!(ler_lista("10, 20,50"), Some(vec![10.0, 20.0, 50.0]));
+        assert_eq!(ler_lista("1..4"), Some(vec![1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(ler_lista("5,10..30:10,"), Some(vec![5.0, 10.0, 20.0, 30.0]));
+        // Passo que não chega exatamente ao fim
+        assert_eq!(ler_lista("1..6:2"), Some(vec![1.0, 3.0, 5.0]));
+        assert_eq!(ler_lista("3..3"), Some(vec![3.0]));
+        assert_eq!(ler_lista("0..999").map(|v| v.len()), Some(1000));
+    }
+
+    #[test]
+    fn ler_lista_passo_fracionario_sem_erro_acumulado() {
+        let esperado = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
+        assert_eq!(ler_lista("0.1..0.9:0.1"), Some(esperado));
+        assert_eq!(ler_lista("0..0.3:0.1"), Some(vec![0.0, 0.1, 0.2, 0.3]));
+    }
+
+    #[test]
+    fn ler_lista_invalida() {
+        for texto in [
+            "",
+            " , ",
+            "dez",
+            "1,,x",
+            "inf",
+            "5..1",
+            "1..5:0",
+            "1..5:-1",
+            "1..",
+            "..5",
+            "1..5:x",
+            "1..2000",
+            "0..999,1000",
+        ] {
+            assert!(ler_lista(texto).is_none(), "{texto:?}");
+        }
+    }
+
+    #[test]
+    fn migrar_csv_preenche_colunas_novas() {
+        let original = format!(
+            "{}\nils,10,2,1,1.5,3,30,20,0.05,100,42,max-iter-sem-melhora,2024-05-01T00:00:00Z,host,uniformes-1.5\n",
+            cabecalho_csv(3)
+        );
+        let arquivo = ArquivoTemporario::com("migrar.csv", &original);
+        let backup = ArquivoTemporario::new("migrar.csv.v3.bak");
+        let caminho = arquivo.texto();
+
+        migrar_csv(&caminho, VERSAO_CSV_ATUAL).unwrap();
+        let mut leitor = csv::Reader::from_path(&caminho).unwrap();
+        let cabecalho = leitor.headers().unwrap().clone();
+        assert_eq!(
+            cabecalho.iter().collect::<Vec<_>>().join(","),
+            cabecalho_csv(VERSAO_CSV_ATUAL)
+        );
+        let linhas: Vec<csv::StringRecord> = leitor.records().map(|r| r.unwrap()).collect();
+        assert_eq!(linhas.len(), 1);
+        let campo = |nome: &str| &linhas[0][cabecalho.iter().position(|c| c == nome).unwrap()];
+        assert_eq!(campo("valor"), "20");
+        assert_eq!(campo("ambiente"), "uniformes-1.5");
+        assert_eq!(campo("objetivo"), "makespan");
+        assert_eq!(campo("migracoes"), "NA");
+        assert_eq!(campo("instancia"), "NA");
+
+        assert_eq!(std::fs::read_to_string(backup.caminho()).unwrap(), original);
+    }
+
+    #[test]
+    fn migrar_csv_recusa_cabecalho_desconhecido() {
+        let arquivo = ArquivoTemporario::com("desconhecido.csv", "heuristica,valor\nils,20\n");
+        let caminho = arquivo.texto();
+
+        let erro = migrar_csv(&caminho, VERSAO_CSV_ATUAL).unwrap_err();
+        assert_eq!(erro.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            std::fs::read_to_string(&caminho).unwrap(),
+            "heuristica,valor\nils,20\n"
+        );
+    }
+
+    #[test]
+    fn salvar_csv_escapa_virgulas_e_aspas() {
+        let arquivo = ArquivoTemporario::new("virgulas.csv");
+        let mut com_virgula = resultado();
+        com_virgula.ambiente = "uniformes,1.5".to_string();
+        com_virgula.referencia = Some(Referencia {
+            nome: "ta01,\"b\"".to_string(),
+            melhor_conhecido: None,
+            otimo: false,
+        });
+        salvar_csv(&com_virgula, arquivo.caminho(), FormatoCsv::Atual).unwrap();
+        salvar_csv(&com_virgula, arquivo.caminho(), FormatoCsv::Atual).unwrap();
+
+        let mut leitor = csv::Reader::from_path(arquivo.caminho()).unwrap();
+        let cabecalho = leitor.headers().unwrap().clone();
+        assert_eq!(
+            cabecalho.iter().collect::<Vec<_>>().join(","),
+            cabecalho_csv(VERSAO_CSV_ATUAL)
+        );
+        let coluna = |nome: &str| cabecalho.iter().position(|c| c == nome).unwrap();
+        let linhas: Vec<csv::StringRecord> = leitor.records().map(|r| r.unwrap()).collect();
+        assert_eq!(linhas.len(), 2);
+        assert_eq!(&linhas[1][coluna("instancia")], "ta01,\"b\"");
+        assert_eq!(&linhas[1][coluna("ambiente")], "uniformes,1.5");
+    }
+}